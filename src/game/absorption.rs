@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{console::ConsoleAppExt, settings::Settings, *};
+
+const NEAR_EQUAL_RATIO: f32 = 0.85;
+const ABSORBED_AREA: f32 = 0.5;
+const TRANSFER_RATE: f32 = 1.5;
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbsorptionMode {
+    #[default]
+    Classic,
+    Partial,
+}
+
+impl AbsorptionMode {
+    pub const ALL: [Self; 2] = [Self::Classic, Self::Partial];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Partial => "partial",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|it| it == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+pub fn overlap(a: &Transform, b: &Transform) -> f32 {
+    let radius_a = ORIGINAL_RADIUS * a.scale.x;
+    let radius_b = ORIGINAL_RADIUS * b.scale.x;
+    let distance = a.translation.truncate().distance(b.translation.truncate());
+    ((radius_a + radius_b - distance) / (2. * radius_a.min(radius_b))).max(0.)
+}
+
+pub fn is_near_equal(a: f32, b: f32) -> bool {
+    a.min(b) / a.max(b) >= NEAR_EQUAL_RATIO
+}
+
+pub fn add_area(scale: f32, area: f32) -> f32 {
+    (scale * scale + area).max(0.).sqrt()
+}
+
+pub fn absorbed_area(scale: f32) -> f32 {
+    ABSORBED_AREA * scale * scale
+}
+
+pub fn transfer_area(smaller: f32, overlap: f32, delta: Duration) -> f32 {
+    let available = (smaller * smaller - MIN_SCALE * MIN_SCALE).max(0.);
+    (TRANSFER_RATE * overlap.min(1.) * smaller * smaller * delta.as_secs_f32()).min(available)
+}
+
+pub fn resize(transform: &mut Transform, scale: f32) {
+    let scale = scale.clamp(MIN_SCALE, MAX_SCALE);
+    transform.scale = Vec3::new(scale, scale, 1.);
+    transform.translation.z = transform.scale.length();
+}
+
+pub struct AbsorptionPlugin {}
+
+impl Plugin for AbsorptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "absorption",
+            "absorption <classic|partial>",
+            absorption_command,
+        );
+    }
+}
+
+impl Default for AbsorptionPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn absorption_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing mode")?;
+    let mode = AbsorptionMode::from_name(name).ok_or_else(|| format!("unknown mode '{}'", name))?;
+    world.resource_mut::<Settings>().absorption = mode;
+    Ok(format!("absorption set to {}", mode.name()))
+}
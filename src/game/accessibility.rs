@@ -0,0 +1,230 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    animation::Animator,
+    atlas::{Atlases, MARBLES_ATLAS},
+    console::ConsoleAppExt,
+    settings::Settings,
+    *,
+};
+
+const NEUTRAL_SPRITE: &str = "ice";
+const GLYPH_SIZE: f32 = 40.;
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorPalette {
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl ColorPalette {
+    pub const ALL: [ColorPalette; 5] = [
+        Self::Default,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+        Self::HighContrast,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Deuteranopia => "deuteranopia",
+            Self::Protanopia => "protanopia",
+            Self::Tritanopia => "tritanopia",
+            Self::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|palette| palette.name() == name)
+    }
+
+    pub fn color(&self, kind: ObstacleKind) -> Color {
+        match (self, kind) {
+            (Self::Default, _) => kind.get_color(),
+            (_, ObstacleKind::Ice) => Color::WHITE,
+            (Self::HighContrast, ObstacleKind::Block) => Color::BLACK,
+            (_, ObstacleKind::Block) => Color::GRAY,
+            (Self::Deuteranopia, ObstacleKind::ScaleBust(true)) => Color::rgb(0., 0.45, 0.7),
+            (Self::Deuteranopia, ObstacleKind::ScaleBust(false)) => Color::rgb(0.9, 0.62, 0.),
+            (Self::Deuteranopia, ObstacleKind::Poison) => Color::rgb(0.8, 0.47, 0.65),
+            (Self::Protanopia, ObstacleKind::ScaleBust(true)) => Color::rgb(0.34, 0.71, 0.91),
+            (Self::Protanopia, ObstacleKind::ScaleBust(false)) => Color::rgb(0.94, 0.89, 0.26),
+            (Self::Protanopia, ObstacleKind::Poison) => Color::rgb(0.55, 0., 0.55),
+            (Self::Tritanopia, ObstacleKind::ScaleBust(true)) => Color::rgb(0., 0.6, 0.65),
+            (Self::Tritanopia, ObstacleKind::ScaleBust(false)) => Color::rgb(0.9, 0.2, 0.2),
+            (Self::Tritanopia, ObstacleKind::Poison) => Color::rgb(0.95, 0.6, 0.75),
+            (Self::HighContrast, ObstacleKind::ScaleBust(true)) => Color::rgb(0., 0.4, 1.),
+            (Self::HighContrast, ObstacleKind::ScaleBust(false)) => Color::rgb(1., 0.85, 0.),
+            (Self::HighContrast, ObstacleKind::Poison) => Color::rgb(1., 0., 1.),
+        }
+    }
+
+    pub fn sprite_name(&self, kind: ObstacleKind) -> &'static str {
+        match self {
+            Self::Default => kind.sprite_name(),
+            _ => NEUTRAL_SPRITE,
+        }
+    }
+
+    pub fn glyph_color(&self, kind: ObstacleKind) -> Color {
+        let color = self.color(kind);
+        if color.r() * 0.3 + color.g() * 0.59 + color.b() * 0.11 > 0.5 {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        }
+    }
+
+    pub fn tint(&self, kind: ObstacleKind) -> Color {
+        match self {
+            Self::Default => Color::WHITE,
+            _ => self.color(kind),
+        }
+    }
+}
+
+pub fn glyph(kind: ObstacleKind) -> &'static str {
+    match kind {
+        ObstacleKind::ScaleBust(true) => "+",
+        ObstacleKind::ScaleBust(false) => "-",
+        ObstacleKind::Block => "#",
+        ObstacleKind::Ice => "*",
+        ObstacleKind::Poison => "X",
+    }
+}
+
+#[derive(Component)]
+pub struct ObstacleGlyph {}
+
+pub struct AccessibilityPlugin {}
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "palette",
+            "palette <default|deuteranopia|protanopia|tritanopia|high-contrast>",
+            palette_command,
+        )
+        .add_console_command("glyphs", "glyphs", glyphs_command)
+        .add_systems(
+            Update,
+            (
+                remove_obstacle_glyph_system.run_if(resource_changed::<Settings>()),
+                obstacle_palette_system,
+                obstacle_glyph_system,
+            )
+                .chain()
+                .after(spawn_obstacle_system),
+        )
+        .add_systems(
+            Update,
+            wall_palette_system.run_if(resource_changed::<Settings>()),
+        );
+    }
+}
+
+impl Default for AccessibilityPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn palette_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing palette")?;
+    let palette =
+        ColorPalette::from_name(name).ok_or_else(|| format!("unknown palette '{}'", name))?;
+    world.resource_mut::<Settings>().palette = palette;
+    Ok(format!("palette set to {}", palette.name()))
+}
+
+fn glyphs_command(_args: &[&str], world: &mut World) -> Result<String, String> {
+    let mut settings = world.resource_mut::<Settings>();
+    settings.glyphs = !settings.glyphs;
+    Ok(format!(
+        "glyphs {}",
+        if settings.glyphs {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    ))
+}
+
+pub fn obstacle_palette_system(
+    settings: Res<Settings>,
+    atlases: Atlases,
+    mut query: Query<(Ref<Obstacle>, &mut TextureAtlasSprite, &mut Animator)>,
+) {
+    for (obstacle, mut sprite, mut animator) in query.iter_mut() {
+        if !obstacle.is_added() && !settings.is_changed() {
+            continue;
+        }
+        let palette = settings.palette;
+        let Some((_, index)) = atlases.sprite(MARBLES_ATLAS, palette.sprite_name(obstacle.kind))
+        else {
+            continue;
+        };
+        sprite.index = index;
+        sprite.color = palette.tint(obstacle.kind);
+        *animator = Animator::obstacle(index);
+    }
+}
+
+pub fn wall_palette_system(settings: Res<Settings>, mut query: Query<&mut Sprite, With<Wall>>) {
+    for mut sprite in query.iter_mut() {
+        sprite.color = settings.palette.color(ObstacleKind::Block);
+    }
+}
+
+pub fn obstacle_glyph_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    query: Query<(Entity, &Obstacle, Option<&Children>)>,
+    glyph_query: Query<(), With<ObstacleGlyph>>,
+) {
+    if !settings.glyphs {
+        return;
+    }
+    for (entity, obstacle, children) in query.iter() {
+        let has_glyph = children
+            .map(|children| children.iter().any(|child| glyph_query.contains(*child)))
+            .unwrap_or(false);
+        if has_glyph {
+            continue;
+        }
+        let glyph_entity = commands
+            .spawn((
+                ObstacleGlyph {},
+                Text2dBundle {
+                    text: Text::from_section(
+                        glyph(obstacle.kind),
+                        TextStyle {
+                            font_size: GLYPH_SIZE,
+                            color: settings.palette.glyph_color(obstacle.kind),
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_translation(Vec3::new(0., 0., 0.1)),
+                    ..default()
+                },
+            ))
+            .id();
+        commands.entity(entity).add_child(glyph_entity);
+    }
+}
+
+pub fn remove_obstacle_glyph_system(
+    mut commands: Commands,
+    query: Query<Entity, With<ObstacleGlyph>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::Duration,
+};
+
+use super::{
+    localization::Localizer,
+    ron_asset::{RonAsset, RonAssetAppExt},
+    save::SaveFile,
+    *,
+};
+
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum AchievementGoal {
+    SurviveSeconds(f32),
+    ReachScale(f32),
+    AbsorbInRun { kind: ObstacleKind, count: u32 },
+    NoSwapSeconds(f32),
+}
+
+impl AchievementGoal {
+    pub fn target(&self) -> f32 {
+        match self {
+            Self::SurviveSeconds(seconds) | Self::NoSwapSeconds(seconds) => *seconds,
+            Self::ReachScale(scale) => *scale,
+            Self::AbsorbInRun { count, .. } => *count as f32,
+        }
+    }
+
+    pub fn progress(&self, stats: &RunStats) -> f32 {
+        match self {
+            Self::SurviveSeconds(_) => stats.elapsed.as_secs_f32(),
+            Self::ReachScale(_) => stats.max_scale,
+            Self::AbsorbInRun { kind, .. } => stats.absorbed(*kind) as f32,
+            Self::NoSwapSeconds(_) => stats.longest_without_swap.as_secs_f32(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub goal: AchievementGoal,
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct AchievementDefinitions {
+    pub achievements: Vec<AchievementDefinition>,
+}
+
+impl RonAsset for AchievementDefinitions {
+    const EXTENSIONS: &'static [&'static str] = &["achievements.ron"];
+}
+
+#[derive(Resource)]
+pub struct AchievementDefinitionsHandle(Handle<AchievementDefinitions>);
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct AchievementSave {
+    pub progress: BTreeMap<String, f32>,
+    pub unlocked: BTreeSet<String>,
+}
+
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub elapsed: Duration,
+    pub max_scale: f32,
+    pub since_swap: Duration,
+    pub longest_without_swap: Duration,
+    absorbed: HashMap<ObstacleKind, u32>,
+}
+
+impl RunStats {
+    pub fn absorbed(&self, kind: ObstacleKind) -> u32 {
+        self.absorbed.get(&kind).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct AchievementUnlocked {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Component)]
+pub struct AchievementToast {
+    timer: Timer,
+}
+
+pub struct AchievementPlugin {}
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<AchievementDefinitions>()
+            .add_event::<AchievementUnlocked>()
+            .init_resource::<RunStats>()
+            .add_systems(Startup, load_achievements_system)
+            .add_systems(OnEnter(GameState::Startup), reset_run_stats_system)
+            .add_systems(
+                Update,
+                (run_stats_system, achievement_progress_system)
+                    .chain()
+                    .run_if(in_state(GameState::Running)),
+            )
+            .add_systems(
+                Update,
+                (achievement_toast_system, achievement_toast_despawn_system),
+            );
+    }
+}
+
+impl Default for AchievementPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn load_achievements_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AchievementDefinitionsHandle(
+        asset_server.load("data/default.achievements.ron"),
+    ));
+}
+
+pub fn reset_run_stats_system(mut commands: Commands) {
+    commands.insert_resource(RunStats::default());
+}
+
+pub fn run_stats_system(
+    time: Res<Time>,
+    mut stats: ResMut<RunStats>,
+    mut events: EventReader<GameplayEvent>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    stats.elapsed += time.delta();
+    stats.since_swap += time.delta();
+    for event in events.read() {
+        match event {
+            GameplayEvent::Absorbed { kind, .. } => {
+                *stats.absorbed.entry(*kind).or_insert(0) += 1;
+            }
+            GameplayEvent::ScaleSwapped { .. } => stats.since_swap = Duration::ZERO,
+            _ => (),
+        }
+    }
+    stats.longest_without_swap = stats.longest_without_swap.max(stats.since_swap);
+    for transform in player_query.iter() {
+        stats.max_scale = stats.max_scale.max(transform.scale.x);
+    }
+}
+
+pub fn achievement_progress_system(
+    handle: Res<AchievementDefinitionsHandle>,
+    definitions: Res<Assets<AchievementDefinitions>>,
+    stats: Res<RunStats>,
+    mut save: ResMut<SaveFile>,
+    mut unlocked_events: EventWriter<AchievementUnlocked>,
+) {
+    let Some(definitions) = definitions.get(&handle.0) else {
+        return;
+    };
+    for definition in definitions.achievements.iter() {
+        let achievements = &save.data().achievements;
+        if achievements.unlocked.contains(&definition.id) {
+            continue;
+        }
+        let progress = definition.goal.progress(&stats);
+        let best = achievements
+            .progress
+            .get(&definition.id)
+            .copied()
+            .unwrap_or(0.);
+        if progress <= best {
+            continue;
+        }
+        let achievements = &mut save.data_mut().achievements;
+        achievements
+            .progress
+            .insert(definition.id.clone(), progress);
+        if progress >= definition.goal.target() {
+            achievements.unlocked.insert(definition.id.clone());
+            unlocked_events.send(AchievementUnlocked {
+                id: definition.id.clone(),
+                name: definition.name.clone(),
+            });
+        }
+    }
+}
+
+pub fn achievement_toast_system(
+    mut commands: Commands,
+    mut events: EventReader<AchievementUnlocked>,
+    localizer: Localizer,
+) {
+    for event in events.read() {
+        let name = localizer
+            .try_get(&format!("achievement.{}", event.id))
+            .unwrap_or(&event.name);
+        commands.spawn((
+            AchievementToast {
+                timer: Timer::new(TOAST_DURATION, TimerMode::Once),
+            },
+            TextBundle::from_section(
+                localizer.format("achievement.unlocked", &[("name", name)]),
+                TextStyle {
+                    font_size: 32.,
+                    color: Color::GOLD,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(8.),
+                left: Val::Percent(5.),
+                ..default()
+            }),
+        ));
+    }
+}
+
+pub fn achievement_toast_despawn_system(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut query: Query<(Entity, &mut AchievementToast)>,
+) {
+    for (entity, mut toast) in query.iter_mut() {
+        toast.timer.tick(time.delta());
+        if toast.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
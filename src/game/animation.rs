@@ -0,0 +1,280 @@
+use bevy::{prelude::*, utils::HashMap};
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum LoopMode {
+    Loop,
+    PingPong,
+    Once,
+    Hold,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationState {
+    Idle,
+    Growing,
+    Shrinking,
+    Frozen,
+    Dying,
+}
+
+impl AnimationState {
+    pub const ALL: [Self; 5] = [
+        Self::Idle,
+        Self::Growing,
+        Self::Shrinking,
+        Self::Frozen,
+        Self::Dying,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Growing => "growing",
+            Self::Shrinking => "shrinking",
+            Self::Frozen => "frozen",
+            Self::Dying => "dying",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub last: usize,
+    pub frame_durations: Vec<Duration>,
+    pub mode: LoopMode,
+}
+
+impl AnimationClip {
+    pub fn uniform(first: usize, last: usize, frame: Duration, mode: LoopMode) -> Self {
+        let frames = first.abs_diff(last) + 1;
+        Self {
+            first,
+            last,
+            frame_durations: vec![frame; frames],
+            mode,
+        }
+    }
+
+    pub fn still(index: usize) -> Self {
+        Self::uniform(index, index, Duration::MAX, LoopMode::Hold)
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.first.abs_diff(self.last) + 1
+    }
+
+    pub fn sprite_index(&self, offset: usize) -> usize {
+        if self.first <= self.last {
+            self.first + offset
+        } else {
+            self.first - offset
+        }
+    }
+
+    pub fn frame_duration(&self, offset: usize) -> Duration {
+        self.frame_durations
+            .get(offset)
+            .or(self.frame_durations.last())
+            .copied()
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+#[derive(Component)]
+pub struct Animator {
+    clips: HashMap<String, AnimationClip>,
+    current: String,
+    offset: usize,
+    elapsed: Duration,
+    forward: bool,
+    finished: bool,
+}
+
+impl Animator {
+    pub fn new(initial: &str, clip: AnimationClip) -> Self {
+        let mut clips = HashMap::default();
+        clips.insert(initial.to_string(), clip);
+        Self {
+            clips,
+            current: initial.to_string(),
+            offset: 0,
+            elapsed: Duration::ZERO,
+            forward: true,
+            finished: false,
+        }
+    }
+
+    pub fn with_clip(mut self, name: &str, clip: AnimationClip) -> Self {
+        self.clips.insert(name.to_string(), clip);
+        self
+    }
+
+    pub fn player(manifest: &atlas::AtlasManifest) -> Self {
+        let idle = AnimationState::Idle.name();
+        let still = AnimationClip::still(manifest.frame(idle).unwrap_or(0));
+        AnimationState::ALL.iter().fold(
+            Self::new(idle, manifest.clip(idle).unwrap_or(still)),
+            |animator, state| match manifest.clip(state.name()) {
+                Some(clip) => animator.with_clip(state.name(), clip),
+                None => animator,
+            },
+        )
+    }
+
+    pub fn obstacle(index: usize) -> Self {
+        Self::new(AnimationState::Idle.name(), AnimationClip::still(index))
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn play(&mut self, name: &str) {
+        if self.current == name || !self.clips.contains_key(name) {
+            return;
+        }
+        self.current = name.to_string();
+        self.offset = 0;
+        self.elapsed = Duration::ZERO;
+        self.forward = true;
+        self.finished = false;
+    }
+
+    pub fn sprite_index(&self) -> Option<usize> {
+        self.clips
+            .get(&self.current)
+            .map(|clip| clip.sprite_index(self.offset))
+    }
+
+    pub fn tick(&mut self, delta: Duration) -> Option<usize> {
+        let clip = self.clips.get(&self.current)?;
+        if self.finished {
+            return Some(clip.sprite_index(self.offset));
+        }
+        self.elapsed += delta;
+        let total = clip.frame_count();
+        while !self.finished
+            && clip.frame_duration(self.offset) > Duration::ZERO
+            && self.elapsed >= clip.frame_duration(self.offset)
+        {
+            self.elapsed -= clip.frame_duration(self.offset);
+            match clip.mode {
+                LoopMode::Loop => self.offset = (self.offset + 1) % total,
+                LoopMode::PingPong => {
+                    if total == 1 {
+                        continue;
+                    }
+                    if self.forward && self.offset + 1 == total {
+                        self.forward = false;
+                    } else if !self.forward && self.offset == 0 {
+                        self.forward = true;
+                    }
+                    if self.forward {
+                        self.offset += 1;
+                    } else {
+                        self.offset -= 1;
+                    }
+                }
+                LoopMode::Once => {
+                    if self.offset + 1 == total {
+                        self.offset = 0;
+                        self.finished = true;
+                    } else {
+                        self.offset += 1;
+                    }
+                }
+                LoopMode::Hold => {
+                    if self.offset + 1 == total {
+                        self.finished = true;
+                    } else {
+                        self.offset += 1;
+                    }
+                }
+            }
+        }
+        Some(clip.sprite_index(self.offset))
+    }
+}
+
+pub struct AnimationPlugin {}
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                player_animation_state_system.run_if(in_state(GameState::Running)),
+                player_dying_animation_system,
+                animation_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+impl Default for AnimationPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn player_animation_state_system(
+    mut query: Query<(Entity, &Scale, &Transform, &mut Animator), With<Player>>,
+    frozen_query: Query<&FrozenEffect>,
+) {
+    for (entity, scale, transform, mut animator) in query.iter_mut() {
+        let size = transform.scale.x;
+        let state = if frozen_query.iter().any(|it| it.target == entity) {
+            AnimationState::Frozen
+        } else if scale.speed > 0. && size < MAX_SCALE {
+            AnimationState::Growing
+        } else if scale.speed < 0. && size > MIN_SCALE {
+            AnimationState::Shrinking
+        } else {
+            AnimationState::Idle
+        };
+        animator.play(state.name());
+    }
+}
+
+pub fn player_dying_animation_system(
+    mut events: EventReader<GameEvent>,
+    mut query: Query<&mut Animator, With<Player>>,
+) {
+    for event in events.read() {
+        if let GameEvent::GameOver = event {
+            for mut animator in query.iter_mut() {
+                animator.play(AnimationState::Dying.name());
+            }
+        }
+    }
+}
+
+pub fn animation_system(
+    time: Res<Time>,
+    real_time: Res<Time<Real>>,
+    state: Res<State<GameState>>,
+    mut query: Query<(&mut Animator, &mut TextureAtlasSprite)>,
+) {
+    let delta = if *state.get() == GameState::Over {
+        real_time.delta()
+    } else {
+        time.delta()
+    };
+    for (mut animator, mut sprite) in query.iter_mut() {
+        if let Some(index) = animator.tick(delta) {
+            if sprite.index != index {
+                sprite.index = index;
+            }
+        }
+    }
+}
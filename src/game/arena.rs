@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::{prelude::*, DelegatedRng};
+use std::time::Duration;
+
+use super::{settings::Settings, *};
+
+pub const ARENA_SIZE: Vec2 = Vec2::new(720., 1080.);
+pub const WALL_THICKNESS: f32 = 32.;
+const OBSTACLE_LIFETIME: Duration = Duration::from_secs(20);
+
+pub struct ArenaPlugin {}
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Startup),
+            (spawn_arena_walls_system, stop_camera_system)
+                .after(reset_camera_system)
+                .run_if(resource_equals(PlayField::Arena)),
+        )
+        .add_systems(
+            Update,
+            expire_arena_obstacle_system
+                .run_if(in_state(GameState::Running))
+                .run_if(resource_equals(PlayField::Arena))
+                .run_if(not(resource_exists::<tutorial::Tutorial>())),
+        );
+    }
+}
+
+impl Default for ArenaPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn arena_inner_half_size() -> Vec2 {
+    ARENA_SIZE / 2. - WALL_THICKNESS
+}
+
+impl ObstacleFactoryComponent {
+    pub fn create_in_arena(
+        &mut self,
+        random: &mut RngComponent,
+        policy: &game_mode::SpawnPolicy,
+        event: &mut EventWriter<SpawnObstacleEvent>,
+    ) {
+        if !self.timer.just_finished() {
+            return;
+        }
+        for _ in 0..policy.per_wave {
+            let scale = 0.75 + random.f32() * 0.50;
+            let half_size = arena_inner_half_size() - ORIGINAL_RADIUS * scale;
+            let along = random.f32_normalized();
+            let position = match random.u8(0..=3) {
+                0 => Vec2::new(along * half_size.x, half_size.y),
+                1 => Vec2::new(along * half_size.x, -half_size.y),
+                2 => Vec2::new(half_size.x, along * half_size.y),
+                _ => Vec2::new(-half_size.x, along * half_size.y),
+            };
+            let kind = policy.kind(random);
+            event.send(SpawnObstacleEvent {
+                color: kind.get_color(),
+                position: position.extend(0.),
+                radius: ORIGINAL_RADIUS,
+                scale,
+                kind,
+            })
+        }
+    }
+}
+
+pub fn spawn_arena_walls_system(mut commands: Commands, settings: Res<Settings>) {
+    let half_size = ARENA_SIZE / 2.;
+    let half_thickness = WALL_THICKNESS / 2.;
+    let walls = [
+        (
+            Vec2::new(0., half_size.y - half_thickness),
+            Vec2::new(half_size.x, half_thickness),
+        ),
+        (
+            Vec2::new(0., -half_size.y + half_thickness),
+            Vec2::new(half_size.x, half_thickness),
+        ),
+        (
+            Vec2::new(half_size.x - half_thickness, 0.),
+            Vec2::new(half_thickness, half_size.y),
+        ),
+        (
+            Vec2::new(-half_size.x + half_thickness, 0.),
+            Vec2::new(half_thickness, half_size.y),
+        ),
+    ];
+    for (position, half_extents) in walls {
+        commands
+            .spawn(Wall {})
+            .insert(Collider::cuboid(half_extents.x, half_extents.y))
+            .insert(RigidBody::Fixed)
+            .insert(CollisionGroups::new(Group::all(), Group::all()))
+            .insert(SolverGroups::new(Group::all(), Group::all()))
+            .insert(SpriteBundle {
+                sprite: Sprite {
+                    color: settings.palette.color(ObstacleKind::Block),
+                    custom_size: Some(half_extents * 2.),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.5)),
+                ..default()
+            });
+    }
+}
+
+pub fn stop_camera_system(mut commands: Commands, query: Query<Entity, With<Camera>>) {
+    for camera in query.iter() {
+        commands.entity(camera).insert(Velocity::zero());
+    }
+}
+
+pub fn expire_arena_obstacle_system(
+    mut commands: Commands,
+    query: Query<Entity, (Added<Obstacle>, Without<Temporary>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(Temporary {
+            timer: Timer::new(OBSTACLE_LIFETIME, TimerMode::Once),
+        });
+    }
+}
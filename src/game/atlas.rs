@@ -0,0 +1,210 @@
+use bevy::{
+    app::AppExit,
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState},
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+use std::{collections::HashMap, time::Duration};
+
+use super::{
+    animation::{AnimationClip, LoopMode},
+    ron_asset::RonAssetLoaderError,
+    *,
+};
+
+pub const MARBLES_ATLAS: &str = "marbles";
+pub const SHADOW_ATLAS: &str = "shadow";
+
+const ATLASES: [(&str, &str); 2] = [
+    (MARBLES_ATLAS, "atlases/marbles.atlas.ron"),
+    (SHADOW_ATLAS, "atlases/shadow.atlas.ron"),
+];
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ClipDescription {
+    pub first: String,
+    pub last: String,
+    #[serde(default)]
+    pub frame_ms: Option<u64>,
+    pub mode: LoopMode,
+}
+
+#[derive(Deserialize)]
+pub struct AtlasManifestDescription {
+    pub image: String,
+    pub tile_size: (f32, f32),
+    pub columns: usize,
+    pub rows: usize,
+    #[serde(default)]
+    pub padding: Option<(f32, f32)>,
+    #[serde(default)]
+    pub offset: Option<(f32, f32)>,
+    pub frames: HashMap<String, usize>,
+    #[serde(default)]
+    pub clips: HashMap<String, ClipDescription>,
+}
+
+#[derive(Asset, TypePath)]
+pub struct AtlasManifest {
+    pub atlas: Handle<TextureAtlas>,
+    pub frames: HashMap<String, usize>,
+    pub clips: HashMap<String, ClipDescription>,
+}
+
+impl AtlasManifest {
+    pub fn frame(&self, name: &str) -> Option<usize> {
+        self.frames.get(name).copied()
+    }
+
+    pub fn clip(&self, name: &str) -> Option<AnimationClip> {
+        let clip = self.clips.get(name)?;
+        let first = self.frame(&clip.first)?;
+        let last = self.frame(&clip.last)?;
+        let frame = clip
+            .frame_ms
+            .map(Duration::from_millis)
+            .unwrap_or(Duration::MAX);
+        Some(AnimationClip::uniform(first, last, frame, clip.mode))
+    }
+}
+
+#[derive(Default)]
+pub struct AtlasManifestLoader;
+
+impl AssetLoader for AtlasManifestLoader {
+    type Asset = AtlasManifest;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let description = ron::de::from_bytes::<AtlasManifestDescription>(&bytes)?;
+            let image: Handle<Image> = load_context.load(description.image);
+            let atlas = TextureAtlas::from_grid(
+                image,
+                Vec2::from(description.tile_size),
+                description.columns,
+                description.rows,
+                description.padding.map(Vec2::from),
+                description.offset.map(Vec2::from),
+            );
+            Ok(AtlasManifest {
+                atlas: load_context.add_labeled_asset("atlas".to_string(), atlas),
+                frames: description.frames,
+                clips: description.clips,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["atlas.ron"]
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct AtlasCache {
+    manifests: HashMap<String, Handle<AtlasManifest>>,
+}
+
+#[derive(SystemParam)]
+pub struct Atlases<'w> {
+    cache: Res<'w, AtlasCache>,
+    manifests: Res<'w, Assets<AtlasManifest>>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl<'w> Atlases<'w> {
+    pub fn get(&self, atlas: &str) -> Option<&AtlasManifest> {
+        self.cache
+            .manifests
+            .get(atlas)
+            .and_then(|handle| self.manifests.get(handle))
+    }
+
+    pub fn sprite(&self, atlas: &str, frame: &str) -> Option<(Handle<TextureAtlas>, usize)> {
+        let manifest = self.get(atlas)?;
+        let index = manifest.frame(frame)?;
+        Some((manifest.atlas.clone(), index))
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.cache
+            .manifests
+            .values()
+            .all(|handle| self.manifests.contains(handle))
+    }
+
+    pub fn failed(&self) -> Vec<&str> {
+        self.cache
+            .manifests
+            .iter()
+            .filter(|(_, handle)| {
+                self.asset_server.get_load_state(*handle) == Some(LoadState::Failed)
+            })
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+pub struct AtlasPlugin {}
+
+impl Plugin for AtlasPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<AtlasManifest>()
+            .init_asset_loader::<AtlasManifestLoader>()
+            .init_resource::<AtlasCache>()
+            .add_systems(Startup, load_atlases_system)
+            .add_systems(
+                Update,
+                atlases_loaded_system.run_if(in_state(GameState::Loading)),
+            );
+    }
+}
+
+impl Default for AtlasPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn load_atlases_system(mut cache: ResMut<AtlasCache>, asset_server: Res<AssetServer>) {
+    for (name, path) in ATLASES {
+        cache
+            .manifests
+            .insert(name.to_string(), asset_server.load(path));
+    }
+}
+
+pub fn atlases_loaded_system(
+    atlases: Atlases,
+    localizer: localization::Localizer,
+    mut state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let failed = atlases.failed();
+    if !failed.is_empty() {
+        error!("could not load atlas manifests {:?}", failed);
+        exit.send(AppExit);
+        return;
+    }
+    if atlases.is_loaded() && localizer.is_loaded() {
+        let failed = localizer.failed();
+        if !failed.is_empty() {
+            error!(
+                "could not load string tables {:?}, falling back to {}",
+                failed,
+                localization::FALLBACK_LANGUAGE
+            );
+        }
+        state.set(GameState::Startup);
+    }
+}
@@ -0,0 +1,371 @@
+use bevy::{
+    audio::{AudioPlugin, AudioSinkPlayback, Volume},
+    prelude::*,
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{console::ConsoleAppExt, settings::Settings, *};
+
+const VOLUME_STEP: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    AbsorbBlock,
+    AbsorbIce,
+    AbsorbPoison,
+    AbsorbGrow,
+    AbsorbShrink,
+    ScaleSwap,
+    Freeze,
+    PoisonDeath,
+    Pause,
+    Resume,
+    GameOver,
+}
+
+impl SoundEffect {
+    pub const ALL: [SoundEffect; 11] = [
+        Self::AbsorbBlock,
+        Self::AbsorbIce,
+        Self::AbsorbPoison,
+        Self::AbsorbGrow,
+        Self::AbsorbShrink,
+        Self::ScaleSwap,
+        Self::Freeze,
+        Self::PoisonDeath,
+        Self::Pause,
+        Self::Resume,
+        Self::GameOver,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::AbsorbBlock => "sounds/absorb_block.wav",
+            Self::AbsorbIce => "sounds/absorb_ice.wav",
+            Self::AbsorbPoison => "sounds/absorb_poison.wav",
+            Self::AbsorbGrow => "sounds/absorb_grow.wav",
+            Self::AbsorbShrink => "sounds/absorb_shrink.wav",
+            Self::ScaleSwap => "sounds/scale_swap.wav",
+            Self::Freeze => "sounds/freeze.wav",
+            Self::PoisonDeath => "sounds/poison_death.wav",
+            Self::Pause => "sounds/pause.wav",
+            Self::Resume => "sounds/resume.wav",
+            Self::GameOver => "sounds/game_over.wav",
+        }
+    }
+
+    pub fn from_gameplay(event: &GameplayEvent) -> Vec<SoundEffect> {
+        match event {
+            GameplayEvent::Absorbed { kind, .. } => match kind {
+                ObstacleKind::Block => vec![Self::AbsorbBlock],
+                ObstacleKind::Ice => vec![Self::AbsorbIce, Self::Freeze],
+                ObstacleKind::Poison => vec![Self::AbsorbPoison],
+                ObstacleKind::ScaleBust(true) => vec![Self::AbsorbGrow],
+                ObstacleKind::ScaleBust(false) => vec![Self::AbsorbShrink],
+            },
+            GameplayEvent::ScaleSwapped { .. } => vec![Self::ScaleSwap],
+            GameplayEvent::Paused => vec![Self::Pause],
+            GameplayEvent::Resumed => vec![Self::Resume],
+            GameplayEvent::Died {
+                cause: DeathCause::Poisoned,
+            } => vec![Self::PoisonDeath],
+            GameplayEvent::EffectApplied { .. } | GameplayEvent::Died { .. } => vec![],
+        }
+    }
+
+    pub fn from_game(event: &GameEvent) -> Vec<SoundEffect> {
+        match event {
+            GameEvent::GameOver => vec![Self::GameOver],
+            GameEvent::Start => vec![],
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct PlaySoundEvent {
+    pub effect: SoundEffect,
+}
+
+#[derive(Resource, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AudioBackend {
+    Bevy,
+    NoOp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.,
+            music: 0.5,
+            effects: 0.8,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn music_volume(&self) -> f32 {
+        self.master * self.music
+    }
+
+    pub fn effects_volume(&self) -> f32 {
+        self.master * self.effects
+    }
+
+    pub fn channel_mut(&mut self, name: &str) -> Option<&mut f32> {
+        match name {
+            "master" => Some(&mut self.master),
+            "music" => Some(&mut self.music),
+            "effects" => Some(&mut self.effects),
+            _ => None,
+        }
+    }
+}
+
+pub fn next_volume(volume: f32) -> f32 {
+    if volume >= 1. {
+        0.
+    } else {
+        ((volume / VOLUME_STEP).floor() * VOLUME_STEP + VOLUME_STEP).min(1.)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SoundAssets {
+    effects: HashMap<SoundEffect, Handle<AudioSource>>,
+    music: Handle<AudioSource>,
+}
+
+#[derive(Component)]
+pub struct Music {}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn output_device_available() -> bool {
+    match rodio::OutputStream::try_default() {
+        Ok(_) => true,
+        Err(error) => {
+            warn!("no audio output device, sounds are disabled: {}", error);
+            false
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn output_device_available() -> bool {
+    true
+}
+
+pub struct GameAudioPlugin {}
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        let backend = if app.is_plugin_added::<AudioPlugin>() && output_device_available() {
+            AudioBackend::Bevy
+        } else {
+            AudioBackend::NoOp
+        };
+        app.add_event::<PlaySoundEvent>()
+            .insert_resource(backend)
+            .add_console_command(
+                "volume",
+                "volume <master|music|effects> <0-100>",
+                volume_command,
+            )
+            .add_systems(Update, sound_dispatch_system)
+            .add_systems(
+                Startup,
+                load_sound_assets_system.run_if(resource_equals(AudioBackend::Bevy)),
+            )
+            .add_systems(
+                OnEnter(GameState::Startup),
+                spawn_music_system.run_if(resource_equals(AudioBackend::Bevy)),
+            )
+            .add_systems(
+                Update,
+                (
+                    play_sound_system.after(sound_dispatch_system),
+                    music_volume_system.run_if(resource_changed::<Settings>()),
+                )
+                    .run_if(resource_equals(AudioBackend::Bevy)),
+            );
+    }
+}
+
+impl Default for GameAudioPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn volume_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing channel")?;
+    let percent = args
+        .get(1)
+        .and_then(|value| value.parse::<f32>().ok())
+        .filter(|value| (0. ..=100.).contains(value))
+        .ok_or("expected a volume between 0 and 100")?;
+    let mut settings = world.resource_mut::<Settings>();
+    let volume = settings
+        .audio
+        .channel_mut(name)
+        .ok_or_else(|| format!("unknown channel '{}'", name))?;
+    *volume = percent / 100.;
+    Ok(format!("{} volume set to {}", name, percent))
+}
+
+pub fn sound_dispatch_system(
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut game_events: EventReader<GameEvent>,
+    mut sounds: EventWriter<PlaySoundEvent>,
+) {
+    for event in gameplay_events.read() {
+        for effect in SoundEffect::from_gameplay(event) {
+            sounds.send(PlaySoundEvent { effect });
+        }
+    }
+    for event in game_events.read() {
+        for effect in SoundEffect::from_game(event) {
+            sounds.send(PlaySoundEvent { effect });
+        }
+    }
+}
+
+pub fn load_sound_assets_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let effects = SoundEffect::ALL
+        .iter()
+        .map(|effect| (*effect, asset_server.load(effect.path())))
+        .collect();
+    commands.insert_resource(SoundAssets {
+        effects,
+        music: asset_server.load("sounds/music.wav"),
+    });
+}
+
+pub fn spawn_music_system(
+    mut commands: Commands,
+    assets: Res<SoundAssets>,
+    settings: Res<Settings>,
+    query: Query<Entity, With<Music>>,
+) {
+    if !query.is_empty() {
+        return;
+    }
+    commands.spawn((
+        Music {},
+        AudioBundle {
+            source: assets.music.clone(),
+            settings: PlaybackSettings::LOOP
+                .with_volume(Volume::new_relative(settings.audio.music_volume())),
+        },
+    ));
+}
+
+pub fn play_sound_system(
+    mut commands: Commands,
+    mut events: EventReader<PlaySoundEvent>,
+    assets: Res<SoundAssets>,
+    settings: Res<Settings>,
+) {
+    for event in events.read() {
+        if let Some(source) = assets.effects.get(&event.effect) {
+            commands.spawn(AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::DESPAWN
+                    .with_volume(Volume::new_relative(settings.audio.effects_volume())),
+            });
+        }
+    }
+}
+
+pub fn music_volume_system(settings: Res<Settings>, query: Query<&AudioSink, With<Music>>) {
+    for sink in query.iter() {
+        sink.set_volume(settings.audio.music_volume());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headless_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_state::<GameState>()
+            .init_resource::<Settings>()
+            .add_event::<GameEvent>()
+            .add_event::<GameplayEvent>()
+            .add_plugins(GameAudioPlugin::default());
+        app
+    }
+
+    fn played(app: &App) -> Vec<SoundEffect> {
+        let events = app.world.resource::<Events<PlaySoundEvent>>();
+        events
+            .get_reader()
+            .read(events)
+            .map(|event| event.effect)
+            .collect()
+    }
+
+    #[test]
+    fn uses_no_op_backend_without_audio_plugin() {
+        let app = headless_app();
+        assert_eq!(*app.world.resource::<AudioBackend>(), AudioBackend::NoOp);
+    }
+
+    #[test]
+    fn dispatches_gameplay_events_to_sounds() {
+        let mut app = headless_app();
+        app.world.send_event(GameplayEvent::Absorbed {
+            kind: ObstacleKind::Ice,
+            position: Vec3::ZERO,
+            scale: 1.,
+        });
+        app.world.send_event(GameEvent::GameOver);
+        app.update();
+        assert_eq!(
+            played(&app),
+            vec![
+                SoundEffect::AbsorbIce,
+                SoundEffect::Freeze,
+                SoundEffect::GameOver
+            ]
+        );
+    }
+
+    #[test]
+    fn plays_poison_death_only_when_poisoned() {
+        let mut app = headless_app();
+        app.world.send_event(GameplayEvent::Absorbed {
+            kind: ObstacleKind::Poison,
+            position: Vec3::ZERO,
+            scale: 1.,
+        });
+        app.update();
+        assert_eq!(played(&app), vec![SoundEffect::AbsorbPoison]);
+
+        app.world.send_event(GameplayEvent::Died {
+            cause: DeathCause::Poisoned,
+        });
+        app.update();
+        assert!(played(&app).contains(&SoundEffect::PoisonDeath));
+    }
+
+    #[test]
+    fn ignores_events_without_sounds() {
+        let mut app = headless_app();
+        app.world.send_event(GameplayEvent::EffectApplied {
+            kind: ObstacleKind::Block,
+        });
+        app.update();
+        assert!(played(&app).is_empty());
+    }
+}
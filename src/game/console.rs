@@ -0,0 +1,341 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+
+use super::*;
+
+const TOGGLE_KEY: KeyCode = KeyCode::Grave;
+const TOGGLE_CHAR: char = '`';
+const HISTORY_LINES: usize = 12;
+
+pub type ConsoleCommandFn = fn(&[&str], &mut World) -> Result<String, String>;
+
+#[derive(Clone, Copy)]
+pub struct ConsoleCommand {
+    pub usage: &'static str,
+    pub run: ConsoleCommandFn,
+}
+
+#[derive(Resource, Default)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<&'static str, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    pub fn register(&mut self, name: &'static str, usage: &'static str, run: ConsoleCommandFn) {
+        self.commands.insert(name, ConsoleCommand { usage, run });
+    }
+
+    pub fn get(&self, name: &str) -> Option<ConsoleCommand> {
+        self.commands.get(name).copied()
+    }
+}
+
+pub trait ConsoleAppExt {
+    fn add_console_command(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        run: ConsoleCommandFn,
+    ) -> &mut Self;
+}
+
+impl ConsoleAppExt for App {
+    fn add_console_command(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        run: ConsoleCommandFn,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .register(name, usage, run);
+        self
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Console {
+    pub open: bool,
+    input: String,
+    history: VecDeque<String>,
+    submitted: Vec<String>,
+}
+
+impl Console {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.history.push_back(line.into());
+        while self.history.len() > HISTORY_LINES {
+            self.history.pop_front();
+        }
+    }
+
+    pub fn submit(&mut self, line: impl Into<String>) {
+        self.submitted.push(line.into());
+    }
+
+    fn to_string(&self) -> String {
+        let mut text = self
+            .history
+            .iter()
+            .fold(String::new(), |text, line| text + line + "\n");
+        text.push_str("> ");
+        text.push_str(&self.input);
+        text.push('_');
+        text
+    }
+}
+
+#[derive(Component)]
+pub struct ConsoleUi {}
+
+pub struct ConsolePlugin {}
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .init_resource::<ConsoleCommands>()
+            .add_console_command("help", "help", help_command)
+            .add_console_command("spawn", "spawn <kind> [scale] [at x,y]", spawn_command)
+            .add_console_command("scale", "scale <value>", scale_command)
+            .add_console_command("godmode", "godmode", godmode_command)
+            .add_console_command("timescale", "timescale <factor>", timescale_command)
+            .add_console_command("state", "state <running|pause|over>", state_command)
+            .add_console_command("seed", "seed <value|random>", seed_command)
+            .add_systems(
+                Update,
+                (
+                    console_input_system,
+                    run_console_commands_system,
+                    console_ui_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+impl Default for ConsolePlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn console_input_system(
+    keys: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut console: ResMut<Console>,
+    mut toggle_actions: ResMut<ToggleActions<PlayerAction>>,
+) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        console.open = !console.open;
+        console.input.clear();
+        toggle_actions.enabled = !console.open;
+        characters.clear();
+        return;
+    }
+    if !console.open {
+        characters.clear();
+        return;
+    }
+    for event in characters.read() {
+        if event.char != TOGGLE_CHAR && !event.char.is_control() {
+            console.input.push(event.char);
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+    if keys.just_pressed(KeyCode::Return) {
+        let line = std::mem::take(&mut console.input);
+        if !line.trim().is_empty() {
+            console.submit(line);
+        }
+    }
+}
+
+pub fn run_console_commands_system(world: &mut World) {
+    let submitted = std::mem::take(&mut world.resource_mut::<Console>().submitted);
+    for line in submitted {
+        let output = run_console_line(&line, world);
+        let mut console = world.resource_mut::<Console>();
+        console.print(format!("> {}", line));
+        match output {
+            Ok(message) if message.is_empty() => (),
+            Ok(message) => console.print(message),
+            Err(message) => console.print(format!("error: {}", message)),
+        }
+    }
+}
+
+fn run_console_line(line: &str, world: &mut World) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((name, args)) = words.split_first() else {
+        return Ok(String::new());
+    };
+    let command = world
+        .resource::<ConsoleCommands>()
+        .get(name)
+        .ok_or_else(|| format!("unknown command '{}', try 'help'", name))?;
+    (command.run)(args, world).map_err(|message| format!("{} (usage: {})", message, command.usage))
+}
+
+pub fn console_ui_system(
+    mut commands: Commands,
+    console: Res<Console>,
+    mut query: Query<(Entity, &mut Text), With<ConsoleUi>>,
+) {
+    if !console.open {
+        for (entity, _) in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let style = TextStyle {
+        font_size: 20.,
+        color: Color::WHITE,
+        ..default()
+    };
+    if let Ok((_, mut text)) = query.get_single_mut() {
+        if console.is_changed() {
+            *text = Text::from_section(console.to_string(), style);
+        }
+        return;
+    }
+    let mut text = TextBundle::from_section(console.to_string(), style)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.),
+            left: Val::Px(0.),
+            right: Val::Px(0.),
+            padding: UiRect::all(Val::Px(8.)),
+            ..default()
+        })
+        .with_background_color(Color::rgba(0., 0., 0., 0.75));
+    text.z_index = ZIndex::Global(100);
+    commands.spawn((ConsoleUi {}, text));
+}
+
+fn parse<T: std::str::FromStr>(value: Option<&&str>, name: &str) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing {}", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid {} '{}'", name, value))
+}
+
+fn parse_position(value: &str) -> Result<Vec2, String> {
+    let (x, y) = value
+        .split_once(',')
+        .ok_or_else(|| format!("invalid position '{}'", value))?;
+    Ok(Vec2::new(
+        parse(Some(&x.trim()), "x")?,
+        parse(Some(&y.trim()), "y")?,
+    ))
+}
+
+fn help_command(_args: &[&str], world: &mut World) -> Result<String, String> {
+    let commands = world.resource::<ConsoleCommands>();
+    Ok(commands
+        .commands
+        .values()
+        .map(|command| command.usage)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn spawn_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing kind")?;
+    let kind = ObstacleKind::from_name(name).ok_or_else(|| format!("unknown kind '{}'", name))?;
+    let mut rest = &args[1..];
+    let mut scale = 1.;
+    if let Some(value) = rest.first().filter(|value| **value != "at") {
+        scale = parse(Some(value), "scale")?;
+        rest = &rest[1..];
+    }
+    let offset = match rest {
+        [] => Vec2::new(0., 200.),
+        ["at", position] => parse_position(position)?,
+        _ => return Err("unexpected arguments".into()),
+    };
+    let mut camera_query = world.query_filtered::<&Transform, With<Camera>>();
+    let camera = camera_query
+        .get_single(world)
+        .map(|transform| transform.translation.truncate())
+        .unwrap_or(Vec2::ZERO);
+    let position = camera + offset;
+    world.send_event(SpawnObstacleEvent {
+        color: kind.get_color(),
+        position: position.extend(0.),
+        radius: ORIGINAL_RADIUS,
+        scale,
+        kind,
+    });
+    Ok(format!("spawned {} at {},{}", name, position.x, position.y))
+}
+
+fn scale_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let value: f32 = parse(args.first(), "scale")?;
+    let value = value.clamp(MIN_SCALE, MAX_SCALE);
+    let mut query = world.query_filtered::<&mut Transform, With<Player>>();
+    let mut transform = query.get_single_mut(world).map_err(|_| "no player")?;
+    transform.scale = Vec3::new(value, value, 1.);
+    transform.translation.z = transform.scale.length();
+    Ok(format!("scale set to {}", value))
+}
+
+fn godmode_command(_args: &[&str], world: &mut World) -> Result<String, String> {
+    let mut god_mode = world.resource_mut::<GodMode>();
+    god_mode.0 = !god_mode.0;
+    Ok(format!(
+        "godmode {}",
+        if god_mode.0 { "enabled" } else { "disabled" }
+    ))
+}
+
+fn timescale_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let factor: f32 = parse(args.first(), "factor")?;
+    if factor < 0. {
+        return Err("factor must not be negative".into());
+    }
+    world
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(factor);
+    Ok(format!("timescale set to {}", factor))
+}
+
+fn state_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let state = *world.resource::<State<GameState>>().get();
+    match args.first() {
+        Some(&"running") if state == GameState::Pause => {
+            world.resource_mut::<Time<Virtual>>().unpause();
+            world.send_event(GameplayEvent::Resumed);
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::Running);
+        }
+        Some(&"pause") if state == GameState::Running => {
+            world.resource_mut::<Time<Virtual>>().pause();
+            world.send_event(GameplayEvent::Paused);
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::Pause);
+        }
+        Some(&"over") if state == GameState::Running => {
+            world.send_event(GameEvent::GameOver);
+        }
+        Some(name) => return Err(format!("cannot switch from {:?} to '{}'", state, name)),
+        None => return Ok(format!("state is {:?}", state)),
+    }
+    Ok(String::new())
+}
+
+fn seed_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let fixed = match args.first() {
+        Some(&"random") => None,
+        value => Some(parse(value, "seed")?),
+    };
+    world.resource_mut::<RunSeedConfig>().fixed = fixed;
+    Ok(match fixed {
+        Some(seed) => format!("next runs use seed {}", seed),
+        None => "next runs use a random seed".into(),
+    })
+}
@@ -0,0 +1,230 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::{prelude::*, DelegatedRng};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+
+use super::{
+    game_mode::{ActiveGameMode, GameModeConfig, GameModeKind, ModeScore},
+    localization::Localizer,
+    save::SaveFile,
+    *,
+};
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_seconds() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unix_seconds() -> u64 {
+    (date_now() / 1000.) as u64
+}
+
+pub fn current_day() -> u64 {
+    unix_seconds() / SECONDS_PER_DAY
+}
+
+fn split_mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DailyModifiers {
+    pub spawn_interval: Duration,
+    pub camera_speed: f32,
+    pub scale_speed: f32,
+}
+
+impl DailyModifiers {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut random = RngComponent::with_seed(seed);
+        Self {
+            spawn_interval: Duration::from_secs_f32(0.6 + random.f32() * 0.6),
+            camera_speed: 60. + random.f32() * 60.,
+            scale_speed: 0.3 + random.f32() * 0.5,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct DailyChallenge {
+    pub day: u64,
+    pub seed: u64,
+    pub modifiers: DailyModifiers,
+    pub first_attempt_only: bool,
+    mode: GameModeKind,
+    attempt: u32,
+    recorded: bool,
+}
+
+impl DailyChallenge {
+    pub fn for_day(day: u64) -> Self {
+        let seed = split_mix(day);
+        Self {
+            day,
+            seed,
+            modifiers: DailyModifiers::from_seed(seed),
+            first_attempt_only: false,
+            mode: GameModeKind::Endless,
+            attempt: 0,
+            recorded: false,
+        }
+    }
+
+    pub fn today() -> Self {
+        Self::for_day(current_day())
+    }
+
+    pub fn with_first_attempt_only(mut self, first_attempt_only: bool) -> Self {
+        self.first_attempt_only = first_attempt_only;
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct DailyRecord {
+    pub best: Option<ModeScore>,
+    pub attempts: u32,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct DailySave {
+    pub records: BTreeMap<(GameModeKind, u64), DailyRecord>,
+}
+
+pub struct DailyPlugin {}
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Loading),
+            fix_daily_seed_system.run_if(resource_exists::<DailyChallenge>()),
+        )
+        .add_systems(
+            OnEnter(GameState::Startup),
+            start_daily_attempt_system.run_if(resource_exists::<DailyChallenge>()),
+        )
+        .add_systems(
+            Update,
+            (apply_daily_modifiers_system, record_daily_attempt_system)
+                .run_if(resource_exists::<DailyChallenge>()),
+        );
+    }
+}
+
+impl Default for DailyPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn fix_daily_seed_system(daily: Res<DailyChallenge>, mut seed_config: ResMut<RunSeedConfig>) {
+    seed_config.fixed = Some(daily.seed);
+}
+
+pub fn start_daily_attempt_system(
+    mut commands: Commands,
+    mut daily: ResMut<DailyChallenge>,
+    mut save: ResMut<SaveFile>,
+    mode_config: Res<GameModeConfig>,
+    query: Query<Entity, With<Camera>>,
+) {
+    daily.mode = mode_config.kind;
+    let key = (daily.mode, daily.day);
+    let record = save.data_mut().daily.records.entry(key).or_default();
+    record.attempts += 1;
+    daily.attempt = record.attempts;
+    daily.recorded = false;
+    for camera in query.iter() {
+        commands.entity(camera).insert(Velocity::linear(Vec2::new(
+            0.,
+            daily.modifiers.camera_speed,
+        )));
+    }
+}
+
+pub fn apply_daily_modifiers_system(
+    daily: Res<DailyChallenge>,
+    mut factory_query: Query<&mut ObstacleFactoryComponent, Added<ObstacleFactoryComponent>>,
+    mut player_query: Query<&mut Scale, Added<Player>>,
+) {
+    for mut factory in factory_query.iter_mut() {
+        factory.timer.set_duration(daily.modifiers.spawn_interval);
+    }
+    for mut scale in player_query.iter_mut() {
+        *scale = Scale::new(daily.modifiers.scale_speed);
+    }
+}
+
+pub fn record_daily_attempt_system(
+    mut commands: Commands,
+    mut daily: ResMut<DailyChallenge>,
+    mut save: ResMut<SaveFile>,
+    mut events: EventReader<GameEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    score_query: Query<&TimeScore>,
+    localizer: Localizer,
+) {
+    let game_over = events
+        .read()
+        .filter(|event| matches!(event, GameEvent::GameOver))
+        .count();
+    if game_over == 0 || daily.recorded {
+        return;
+    }
+    daily.recorded = true;
+    let score = ModeScore::best(
+        score_query
+            .iter()
+            .map(|score| ModeScore::of(mode.as_deref(), score)),
+    );
+    let key = (daily.mode, daily.day);
+    let record = save.data_mut().daily.records.entry(key).or_default();
+    if let Some(score) = score {
+        let counted = daily.attempt == 1 || !daily.first_attempt_only;
+        if counted && record.best.map_or(true, |best| score.beats(&best)) {
+            record.best = Some(score);
+        }
+    }
+    let best = record
+        .best
+        .map(|best| best.to_string())
+        .unwrap_or_else(|| "--".to_string());
+    commands.spawn(
+        TextBundle::from_section(
+            localizer.format(
+                "daily.best",
+                &[("day", &daily.day.to_string()), ("time", &best)],
+            ),
+            TextStyle {
+                color: Color::DARK_GRAY,
+                font_size: 32.,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(112.),
+            right: Val::Percent(1.),
+            ..default()
+        }),
+    );
+}
@@ -0,0 +1,174 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+use std::{collections::BTreeMap, fmt::Write};
+
+use super::{console::ConsoleAppExt, *};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+const ARROW_LENGTH: f32 = 64.;
+const ARROW_HEAD: f32 = 12.;
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+}
+
+#[derive(Component)]
+pub struct DebugOverlayText {}
+
+pub struct DebugOverlayPlugin {}
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.init_resource::<DebugOverlay>()
+            .add_console_command("overlay", "overlay", overlay_command)
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay_system,
+                    (debug_overlay_text_system, enemy_strategy_gizmo_system)
+                        .run_if(|overlay: Res<DebugOverlay>| overlay.enabled),
+                    despawn_debug_overlay_system
+                        .run_if(|overlay: Res<DebugOverlay>| !overlay.enabled),
+                )
+                    .chain(),
+            );
+    }
+}
+
+impl Default for DebugOverlayPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn overlay_command(_args: &[&str], world: &mut World) -> Result<String, String> {
+    let mut overlay = world.resource_mut::<DebugOverlay>();
+    overlay.enabled = !overlay.enabled;
+    Ok(format!(
+        "overlay {}",
+        if overlay.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    ))
+}
+
+pub fn toggle_debug_overlay_system(keys: Res<Input<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+pub fn despawn_debug_overlay_system(
+    mut commands: Commands,
+    query: Query<Entity, With<DebugOverlayText>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn debug_overlay_text_system(
+    mut commands: Commands,
+    diagnostics: Res<DiagnosticsStore>,
+    obstacle_query: Query<&Obstacle>,
+    player_query: Query<(Entity, &Transform, &Scale), With<Player>>,
+    bust_query: Query<(&BustEffect, &Temporary)>,
+    frozen_query: Query<(&FrozenEffect, &Temporary)>,
+    mut text_query: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    let mut content = String::new();
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.);
+    let _ = writeln!(content, "fps {:.0}", fps);
+
+    let mut counts = BTreeMap::new();
+    for obstacle in obstacle_query.iter() {
+        *counts.entry(obstacle.kind.sprite_name()).or_insert(0) += 1;
+    }
+    for (name, count) in counts {
+        let _ = writeln!(content, "{} {}", name, count);
+    }
+
+    for (player, transform, scale) in player_query.iter() {
+        let _ = writeln!(
+            content,
+            "scale {:.2} speed {:.0} {}",
+            transform.scale.x,
+            calc_speed(transform),
+            if scale.speed >= 0. {
+                "growing"
+            } else {
+                "shrinking"
+            }
+        );
+        for (effect, temporary) in bust_query.iter().filter(|(it, _)| it.target == player) {
+            let _ = writeln!(
+                content,
+                "bust {:+.2} {:.1}s",
+                effect.speed,
+                temporary.timer.remaining_secs()
+            );
+        }
+        for (_, temporary) in frozen_query.iter().filter(|(it, _)| it.target == player) {
+            let _ = writeln!(content, "frozen {:.1}s", temporary.timer.remaining_secs());
+        }
+    }
+
+    let style = TextStyle {
+        font_size: 20.,
+        color: Color::YELLOW,
+        ..default()
+    };
+    if let Ok(mut text) = text_query.get_single_mut() {
+        *text = Text::from_section(content, style);
+        return;
+    }
+    commands.spawn((
+        DebugOverlayText {},
+        TextBundle::from_section(content, style).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(8.),
+            left: Val::Px(8.),
+            ..default()
+        }),
+    ));
+}
+
+pub fn enemy_strategy_gizmo_system(
+    mut gizmos: Gizmos,
+    enemy_query: Query<(&Enemy, &Transform)>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    for (enemy, transform) in enemy_query.iter() {
+        let (strategy, direction) = enemy.choose(transform, player_transform);
+        let start = transform.translation.truncate();
+        let color = match strategy {
+            Strategy::None => Color::GRAY,
+            Strategy::Follow { .. } => Color::RED,
+            Strategy::Run { .. } => Color::GREEN,
+        };
+        if direction == Vec2::ZERO {
+            gizmos.circle_2d(start, ARROW_HEAD, color);
+            continue;
+        }
+        let end = start + direction * ARROW_LENGTH;
+        gizmos.line_2d(start, end, color);
+        for angle in [PI * 0.75, -PI * 0.75] {
+            let head = Vec2::from_angle(angle).rotate(direction) * ARROW_HEAD;
+            gizmos.line_2d(end, end + head, color);
+        }
+    }
+}
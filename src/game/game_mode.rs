@@ -0,0 +1,492 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::{prelude::*, DelegatedRng};
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+use super::{console::ConsoleAppExt, localization::Localizer, *};
+
+const TIME_ATTACK_LIMIT: Duration = Duration::from_secs(120);
+const SURVIVAL_LIVES: u32 = 3;
+const RECOVERY_TIME: f32 = 2.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Loss {
+    GameOver,
+    Recover,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModeScore {
+    Time(Duration),
+    Points(u32),
+}
+
+impl Default for ModeScore {
+    fn default() -> Self {
+        Self::Time(Duration::ZERO)
+    }
+}
+
+impl ModeScore {
+    pub fn of(mode: Option<&ActiveGameMode>, score: &TimeScore) -> Self {
+        match mode {
+            Some(mode) => mode.mode.score(score),
+            None => Self::Time(score.elapsed()),
+        }
+    }
+
+    pub fn beats(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Time(score), Self::Time(other)) => score > other,
+            (Self::Points(score), Self::Points(other)) => score > other,
+            _ => false,
+        }
+    }
+
+    pub fn best(scores: impl Iterator<Item = Self>) -> Option<Self> {
+        scores.reduce(|best, score| if score.beats(&best) { score } else { best })
+    }
+}
+
+impl fmt::Display for ModeScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Time(duration) => write!(f, "{}", format_time(*duration)),
+            Self::Points(points) => write!(f, "{}", points),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameModeState {
+    Endless,
+    TimeAttack { remaining: Duration, points: u32 },
+    Survival { lives: u32 },
+    Zen,
+}
+
+impl GameModeState {
+    pub fn kind(&self) -> GameModeKind {
+        match self {
+            Self::Endless => GameModeKind::Endless,
+            Self::TimeAttack { .. } => GameModeKind::TimeAttack,
+            Self::Survival { .. } => GameModeKind::Survival,
+            Self::Zen => GameModeKind::Zen,
+        }
+    }
+
+    pub fn restore(&self) -> Box<dyn GameMode> {
+        match *self {
+            Self::Endless => Box::new(Endless {}),
+            Self::TimeAttack { remaining, points } => Box::new(TimeAttack { remaining, points }),
+            Self::Survival { lives } => Box::new(Survival { lives }),
+            Self::Zen => Box::new(Zen {}),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpawnPolicy {
+    pub interval: f32,
+    pub per_wave: usize,
+    pub poison: bool,
+}
+
+impl Default for SpawnPolicy {
+    fn default() -> Self {
+        Self {
+            interval: 1.,
+            per_wave: 2,
+            poison: true,
+        }
+    }
+}
+
+impl SpawnPolicy {
+    pub fn kind(&self, random: &mut RngComponent) -> ObstacleKind {
+        match ObstacleKind::random(random) {
+            ObstacleKind::Poison if !self.poison => ObstacleKind::ScaleBust(random.bool()),
+            kind => kind,
+        }
+    }
+}
+
+pub trait GameMode: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    fn state(&self) -> GameModeState;
+
+    fn spawn_policy(&self) -> SpawnPolicy {
+        SpawnPolicy::default()
+    }
+
+    fn on_loss(&mut self, _cause: DeathCause) -> Loss {
+        Loss::GameOver
+    }
+
+    fn on_absorbed(&mut self, _kind: ObstacleKind, _scale: f32) {}
+
+    fn tick(&mut self, _delta: Duration) {}
+
+    fn is_won(&self) -> bool {
+        false
+    }
+
+    fn score(&self, score: &TimeScore) -> ModeScore {
+        ModeScore::Time(score.elapsed())
+    }
+
+    fn hud(&self, _localizer: &Localizer) -> Option<String> {
+        None
+    }
+
+    fn over_key(&self) -> &'static str {
+        "game-over"
+    }
+}
+
+pub struct Endless {}
+
+impl GameMode for Endless {
+    fn name(&self) -> &'static str {
+        "endless"
+    }
+
+    fn state(&self) -> GameModeState {
+        GameModeState::Endless
+    }
+}
+
+pub struct TimeAttack {
+    remaining: Duration,
+    points: u32,
+}
+
+impl GameMode for TimeAttack {
+    fn name(&self) -> &'static str {
+        "time-attack"
+    }
+
+    fn state(&self) -> GameModeState {
+        GameModeState::TimeAttack {
+            remaining: self.remaining,
+            points: self.points,
+        }
+    }
+
+    fn spawn_policy(&self) -> SpawnPolicy {
+        SpawnPolicy {
+            interval: 0.75,
+            per_wave: 3,
+            ..default()
+        }
+    }
+
+    fn on_absorbed(&mut self, kind: ObstacleKind, scale: f32) {
+        if kind != ObstacleKind::Poison {
+            self.points += (scale * 100.).round() as u32;
+        }
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        self.remaining = self.remaining.saturating_sub(delta);
+    }
+
+    fn is_won(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    fn score(&self, _score: &TimeScore) -> ModeScore {
+        ModeScore::Points(self.points)
+    }
+
+    fn hud(&self, localizer: &Localizer) -> Option<String> {
+        Some(localizer.format("mode.time-left", &[("time", &format_time(self.remaining))]))
+    }
+
+    fn over_key(&self) -> &'static str {
+        if self.is_won() {
+            "mode.time-up"
+        } else {
+            "game-over"
+        }
+    }
+}
+
+pub struct Survival {
+    lives: u32,
+}
+
+impl GameMode for Survival {
+    fn name(&self) -> &'static str {
+        "survival"
+    }
+
+    fn state(&self) -> GameModeState {
+        GameModeState::Survival { lives: self.lives }
+    }
+
+    fn on_loss(&mut self, _cause: DeathCause) -> Loss {
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives == 0 {
+            Loss::GameOver
+        } else {
+            Loss::Recover
+        }
+    }
+
+    fn hud(&self, localizer: &Localizer) -> Option<String> {
+        Some(localizer.format("mode.lives", &[("lives", &self.lives.to_string())]))
+    }
+}
+
+pub struct Zen {}
+
+impl GameMode for Zen {
+    fn name(&self) -> &'static str {
+        "zen"
+    }
+
+    fn state(&self) -> GameModeState {
+        GameModeState::Zen
+    }
+
+    fn spawn_policy(&self) -> SpawnPolicy {
+        SpawnPolicy {
+            interval: 1.5,
+            poison: false,
+            ..default()
+        }
+    }
+
+    fn on_loss(&mut self, _cause: DeathCause) -> Loss {
+        Loss::Recover
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GameModeKind {
+    #[default]
+    Endless,
+    TimeAttack,
+    Survival,
+    Zen,
+}
+
+impl GameModeKind {
+    pub const ALL: [Self; 4] = [Self::Endless, Self::TimeAttack, Self::Survival, Self::Zen];
+
+    pub fn create(&self) -> Box<dyn GameMode> {
+        match self {
+            Self::Endless => Box::new(Endless {}),
+            Self::TimeAttack => Box::new(TimeAttack {
+                remaining: TIME_ATTACK_LIMIT,
+                points: 0,
+            }),
+            Self::Survival => Box::new(Survival {
+                lives: SURVIVAL_LIVES,
+            }),
+            Self::Zen => Box::new(Zen {}),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.create().name() == name)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GameModeConfig {
+    pub kind: GameModeKind,
+}
+
+#[derive(Resource)]
+pub struct ActiveGameMode {
+    pub mode: Box<dyn GameMode>,
+    finished: bool,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct LossEvent {
+    pub cause: DeathCause,
+}
+
+#[derive(Component)]
+pub struct Recovering {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct GameModeHud {}
+
+pub struct GameModePlugin {}
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LossEvent>()
+            .init_resource::<GameModeConfig>()
+            .add_console_command(
+                "mode",
+                "mode <endless|time-attack|survival|zen>",
+                mode_command,
+            )
+            .add_systems(OnEnter(GameState::Startup), start_game_mode_system)
+            .add_systems(
+                Update,
+                (
+                    apply_spawn_policy_system
+                        .after(daily::apply_daily_modifiers_system)
+                        .before(snapshot::apply_snapshot_system),
+                    game_mode_tick_system,
+                    loss_system,
+                    recovering_system,
+                    game_mode_hud_system,
+                )
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
+
+impl Default for GameModePlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn mode_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing mode")?;
+    let kind = GameModeKind::from_name(name).ok_or_else(|| format!("unknown mode '{}'", name))?;
+    world.resource_mut::<GameModeConfig>().kind = kind;
+    Ok(format!("mode set to {}, restart to play it", name))
+}
+
+pub fn start_game_mode_system(mut commands: Commands, config: Res<GameModeConfig>) {
+    commands.insert_resource(ActiveGameMode {
+        mode: config.kind.create(),
+        finished: false,
+    });
+    commands.spawn((
+        GameModeHud {},
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 32.,
+                color: Color::DARK_GRAY,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(72.),
+            right: Val::Percent(1.),
+            ..default()
+        }),
+    ));
+}
+
+pub fn apply_spawn_policy_system(
+    mode: Res<ActiveGameMode>,
+    mut query: Query<&mut ObstacleFactoryComponent, Added<ObstacleFactoryComponent>>,
+) {
+    let policy = mode.mode.spawn_policy();
+    for mut factory in query.iter_mut() {
+        let duration = factory.timer.duration().mul_f32(policy.interval);
+        factory.timer.set_duration(duration);
+    }
+}
+
+pub fn game_mode_tick_system(
+    time: Res<Time>,
+    mut mode: ResMut<ActiveGameMode>,
+    mut events: EventWriter<GameEvent>,
+    mut gameplay_events: EventReader<GameplayEvent>,
+) {
+    if mode.finished {
+        return;
+    }
+    for event in gameplay_events.read() {
+        if let GameplayEvent::Absorbed { kind, scale, .. } = *event {
+            mode.mode.on_absorbed(kind, scale);
+        }
+    }
+    mode.mode.tick(time.delta());
+    if mode.mode.is_won() {
+        mode.finished = true;
+        events.send(GameEvent::GameOver);
+    }
+}
+
+pub fn loss_system(
+    mut commands: Commands,
+    mut mode: ResMut<ActiveGameMode>,
+    mut losses: EventReader<LossEvent>,
+    mut events: EventWriter<GameEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    camera_query: Query<&Transform, (With<Camera>, Without<Player>)>,
+    mut player_query: Query<(Entity, &mut Transform, Option<&Recovering>), With<Player>>,
+) {
+    let Some(loss) = losses.read().next().copied() else {
+        return;
+    };
+    losses.clear();
+    if mode.finished {
+        return;
+    }
+    let Ok((player, mut transform, recovering)) = player_query.get_single_mut() else {
+        return;
+    };
+    if recovering.is_some() {
+        return;
+    }
+    match mode.mode.on_loss(loss.cause) {
+        Loss::GameOver => {
+            mode.finished = true;
+            events.send(GameEvent::GameOver);
+            gameplay_events.send(GameplayEvent::Died { cause: loss.cause });
+        }
+        Loss::Recover => {
+            if let Ok(camera_transform) = camera_query.get_single() {
+                transform.translation = camera_transform.translation.truncate().extend(1.);
+            }
+            absorption::resize(&mut transform, 1.);
+            commands.entity(player).insert((
+                Velocity::zero(),
+                Recovering {
+                    timer: Timer::from_seconds(RECOVERY_TIME, TimerMode::Once),
+                },
+            ));
+        }
+    }
+}
+
+pub fn recovering_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Recovering, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut recovering, mut sprite) in query.iter_mut() {
+        recovering.timer.tick(time.delta());
+        let visible = (recovering.timer.elapsed_secs() * 8.) as u32 % 2 == 0;
+        sprite.color.set_a(if visible { 1. } else { 0.4 });
+        if recovering.timer.finished() {
+            sprite.color.set_a(1.);
+            commands.entity(entity).remove::<Recovering>();
+        }
+    }
+}
+
+pub fn game_mode_hud_system(
+    mode: Res<ActiveGameMode>,
+    localizer: Localizer,
+    mut query: Query<&mut Text, With<GameModeHud>>,
+) {
+    let hud = mode.mode.hud(&localizer).unwrap_or_default();
+    for mut text in query.iter_mut() {
+        if text.sections[0].value != hud {
+            text.sections[0].value = hud.clone();
+        }
+    }
+}
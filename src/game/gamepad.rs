@@ -0,0 +1,198 @@
+use bevy::{
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+};
+use leafwing_input_manager::{plugin::InputManagerSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{localization::LocalizedText, settings::Settings, *};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GamepadIdentity {
+    pub name: String,
+    pub index: usize,
+}
+
+impl GamepadIdentity {
+    pub fn of(gamepad: Gamepad, gamepads: &Gamepads) -> Option<Self> {
+        let name = gamepads.name(gamepad)?;
+        let index = named(gamepads, name).iter().position(|it| *it == gamepad)?;
+        Some(Self {
+            name: name.to_string(),
+            index,
+        })
+    }
+
+    pub fn resolve(&self, gamepads: &Gamepads) -> Option<Gamepad> {
+        named(gamepads, &self.name).get(self.index).copied()
+    }
+}
+
+fn named(gamepads: &Gamepads, name: &str) -> Vec<Gamepad> {
+    let mut matching: Vec<Gamepad> = gamepads
+        .iter()
+        .filter(|gamepad| gamepads.name(*gamepad) == Some(name))
+        .collect();
+    matching.sort_by_key(|gamepad| gamepad.id);
+    matching
+}
+
+#[derive(Component)]
+pub struct GamepadJoinPrompt {}
+
+pub struct GamepadPlugin {}
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            join_gamepad_system.in_set(InputManagerSystem::ManualControl),
+        )
+        .add_systems(
+            Update,
+            (
+                gamepad_connection_system.before(steering::update_input_map_system),
+                gamepad_join_prompt_system,
+            ),
+        );
+    }
+}
+
+impl Default for GamepadPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn is_assigned(settings: &Settings, gamepads: &Gamepads, gamepad: Gamepad) -> bool {
+    settings
+        .gamepads
+        .values()
+        .any(|identity| identity.resolve(gamepads) == Some(gamepad))
+}
+
+pub fn player_gamepad(
+    settings: &Settings,
+    gamepads: &Gamepads,
+    slot: PlayerSlot,
+) -> Option<Gamepad> {
+    if settings.gamepads.contains_key(&slot.0) {
+        return settings.gamepad(slot, gamepads);
+    }
+    gamepads
+        .iter()
+        .filter(|gamepad| !is_assigned(settings, gamepads, *gamepad))
+        .min_by_key(|gamepad| gamepad.id)
+}
+
+fn open_slot(
+    settings: &Settings,
+    gamepads: &Gamepads,
+    slots: impl Iterator<Item = PlayerSlot>,
+) -> Option<PlayerSlot> {
+    let mut slots: Vec<PlayerSlot> = slots.collect();
+    slots.sort();
+    slots
+        .into_iter()
+        .find(|slot| settings.gamepad(*slot, gamepads).is_none())
+}
+
+pub fn gamepad_connection_system(
+    player_query: Query<&InputMap<PlayerAction>, With<Player>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut events: EventReader<GamepadConnectionEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for event in events.read() {
+        match &event.connection {
+            GamepadConnection::Connected(info) => {
+                info!("gamepad {} connected: {}", event.gamepad.id, info.name);
+            }
+            GamepadConnection::Disconnected => {
+                info!("gamepad {} disconnected", event.gamepad.id);
+                let lost = player_query
+                    .iter()
+                    .any(|input_map| input_map.gamepad() == Some(event.gamepad));
+                if lost && *state.get() == GameState::Running && !time.is_paused() {
+                    next_state.set(GameState::Pause);
+                    time.pause();
+                    gameplay_events.send(GameplayEvent::Paused);
+                }
+            }
+        }
+    }
+}
+
+pub fn join_gamepad_system(
+    buttons: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut settings: ResMut<Settings>,
+    mut player_query: Query<(&PlayerSlot, &mut ActionState<PlayerAction>), With<Player>>,
+) {
+    for gamepad in gamepads.iter() {
+        if !buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start)) {
+            continue;
+        }
+        if is_assigned(&settings, &gamepads, gamepad) {
+            continue;
+        }
+        let Some(identity) = GamepadIdentity::of(gamepad, &gamepads) else {
+            continue;
+        };
+        let slots = player_query.iter().map(|(slot, _)| *slot);
+        let Some(slot) = open_slot(&settings, &gamepads, slots) else {
+            continue;
+        };
+        settings.gamepads.insert(slot.0, identity);
+        info!("gamepad {} joined as player {}", gamepad.id, slot.0 + 1);
+        for (_, mut action_state) in player_query.iter_mut().filter(|(it, _)| **it == slot) {
+            action_state.consume(PlayerAction::Pause);
+            action_state.consume(PlayerAction::Start);
+        }
+    }
+}
+
+pub fn gamepad_join_prompt_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    gamepads: Res<Gamepads>,
+    player_query: Query<&PlayerSlot, With<Player>>,
+    prompt_query: Query<Entity, With<GamepadJoinPrompt>>,
+) {
+    let unassigned = gamepads
+        .iter()
+        .any(|gamepad| !is_assigned(&settings, &gamepads, gamepad));
+    let open = open_slot(&settings, &gamepads, player_query.iter().copied()).is_some();
+    let visible = unassigned && open;
+    if visible == !prompt_query.is_empty() {
+        return;
+    }
+    if !visible {
+        for entity in prompt_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    commands.spawn((
+        GamepadJoinPrompt {},
+        LocalizedText {
+            key: "gamepad.join",
+        },
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 32.,
+                color: Color::DARK_GRAY,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(15.),
+            left: Val::Percent(5.),
+            ..default()
+        }),
+    ));
+}
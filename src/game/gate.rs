@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::time::Duration;
+
+use super::{console::ConsoleAppExt, *};
+
+const GATE_BONUS: Duration = Duration::from_secs(5);
+
+#[derive(Resource, Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum GateFailure {
+    #[default]
+    Block,
+    GameOver,
+}
+
+#[derive(Resource, Default)]
+pub struct PassedGates(pub Vec<i64>);
+
+#[derive(Component)]
+pub struct ScaleGate {
+    min: f32,
+    max: f32,
+    passed: bool,
+    solid: bool,
+}
+
+impl ScaleGate {
+    pub fn new(min: f32, max: f32) -> Self {
+        Self {
+            min,
+            max,
+            passed: false,
+            solid: false,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    pub fn fits(&self, transform: &Transform) -> bool {
+        let scale = transform.scale.x;
+        scale >= self.min && scale <= self.max
+    }
+}
+
+pub struct GatePlugin {}
+
+impl Plugin for GatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GateFailure>()
+            .init_resource::<PassedGates>()
+            .add_console_command(
+                "gatefail",
+                "gatefail <block|gameover>",
+                gate_failure_command,
+            )
+            .add_systems(OnEnter(GameState::Startup), reset_passed_gates_system)
+            .add_systems(
+                Update,
+                (
+                    restore_passed_gates_system,
+                    gate_barrier_system,
+                    scale_gate_system,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
+
+impl Default for GatePlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn gate_failure_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let failure = match args.first() {
+        Some(&"block") => GateFailure::Block,
+        Some(&"gameover") => GateFailure::GameOver,
+        _ => return Err("expected block or gameover".into()),
+    };
+    world.insert_resource(failure);
+    Ok(format!("gate failure set to {:?}", failure))
+}
+
+pub fn spawn_scale_gate(
+    commands: &mut Commands,
+    transform: Transform,
+    half_width: f32,
+    min: f32,
+    max: f32,
+) -> Entity {
+    commands
+        .spawn(ScaleGate::new(min, max))
+        .insert(Collider::cuboid(half_width, 8.))
+        .insert(Sensor)
+        .insert(RigidBody::Fixed)
+        .insert(CollisionGroups::new(Group::all(), Group::all()))
+        .insert(SolverGroups::new(Group::all(), Group::all()))
+        .insert(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(1., 1., 0., 0.3),
+                custom_size: Some(Vec2::new(half_width * 2., 16.)),
+                ..default()
+            },
+            transform,
+            ..default()
+        })
+        .id()
+}
+
+pub fn reset_passed_gates_system(mut passed: ResMut<PassedGates>) {
+    passed.0.clear();
+}
+
+pub fn restore_passed_gates_system(
+    passed: Res<PassedGates>,
+    mut query: Query<(&mut ScaleGate, &terrain::TerrainChunk), Added<ScaleGate>>,
+) {
+    for (mut gate, chunk) in query.iter_mut() {
+        if passed.0.contains(&chunk.index()) {
+            gate.passed = true;
+        }
+    }
+}
+
+pub fn gate_barrier_system(
+    mut commands: Commands,
+    failure: Res<GateFailure>,
+    player_query: Query<&Transform, With<Player>>,
+    mut gate_query: Query<(Entity, &mut ScaleGate)>,
+) {
+    if *failure != GateFailure::Block {
+        return;
+    }
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    for (gate_entity, mut gate) in gate_query.iter_mut() {
+        let solid = !gate.passed && !gate.fits(player_transform);
+        if solid == gate.solid {
+            continue;
+        }
+        gate.solid = solid;
+        if solid {
+            commands.entity(gate_entity).remove::<Sensor>();
+        } else {
+            commands.entity(gate_entity).insert(Sensor);
+        }
+    }
+}
+
+pub fn scale_gate_system(
+    rapier_context: Res<RapierContext>,
+    failure: Res<GateFailure>,
+    god_mode: Res<GodMode>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut gate_query: Query<(Entity, &mut ScaleGate)>,
+    mut score_query: Query<&mut TimeScore>,
+    mut losses: EventWriter<game_mode::LossEvent>,
+) {
+    for (player_entity, player_transform) in player_query.iter() {
+        for (gate_entity, mut gate) in gate_query.iter_mut() {
+            if gate.passed || gate.solid {
+                continue;
+            }
+            if rapier_context.intersection_pair(gate_entity, player_entity) != Some(true) {
+                continue;
+            }
+            gate.passed = true;
+            if gate.fits(player_transform) {
+                for mut score in score_query.iter_mut() {
+                    score.add_bonus(GATE_BONUS);
+                }
+            } else if *failure == GateFailure::GameOver && !god_mode.0 {
+                losses.send(game_mode::LossEvent {
+                    cause: DeathCause::Gate,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+
+use super::{
+    animation::{AnimationState, Animator},
+    atlas::{Atlases, SHADOW_ATLAS},
+    game_mode::{ActiveGameMode, GameModeConfig, GameModeKind, ModeScore},
+    save::SaveFile,
+    *,
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+const GHOST_ALPHA: f32 = 0.35;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct GhostSample {
+    pub time: f32,
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+}
+
+impl GhostSample {
+    pub fn lerp(&self, other: &GhostSample, time: f32) -> GhostSample {
+        let span = other.time - self.time;
+        let t = if span > 0. {
+            ((time - self.time) / span).clamp(0., 1.)
+        } else {
+            0.
+        };
+        GhostSample {
+            time,
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            scale: self.scale + (other.scale - self.scale) * t,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct GhostRun {
+    pub duration: f32,
+    pub score: ModeScore,
+    pub samples: Vec<GhostSample>,
+}
+
+impl GhostRun {
+    pub fn sample_at(&self, time: f32) -> Option<GhostSample> {
+        if time > self.duration {
+            return None;
+        }
+        let next = self.samples.partition_point(|sample| sample.time <= time);
+        match (next.checked_sub(1), self.samples.get(next)) {
+            (Some(previous), Some(next)) => Some(self.samples[previous].lerp(next, time)),
+            (Some(previous), None) => Some(self.samples[previous]),
+            (None, Some(next)) => Some(*next),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct GhostSave {
+    pub runs: BTreeMap<(GameModeKind, u64), GhostRun>,
+}
+
+#[derive(Resource, Default)]
+pub struct GhostRecorder {
+    seed: Option<u64>,
+    mode: GameModeKind,
+    elapsed: Duration,
+    timer: Timer,
+    run: GhostRun,
+    finished: bool,
+}
+
+#[derive(Component)]
+pub struct Ghost {
+    run: GhostRun,
+    last_scale: f32,
+}
+
+pub struct GhostPlugin {}
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GhostRecorder>()
+            .add_systems(
+                OnEnter(GameState::Startup),
+                (reset_ghost_recorder_system, spawn_ghost_system),
+            )
+            .add_systems(
+                Update,
+                (record_ghost_system, ghost_playback_system)
+                    .chain()
+                    .run_if(in_state(GameState::Running)),
+            )
+            .add_systems(Update, store_ghost_system);
+    }
+}
+
+impl Default for GhostPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn reset_ghost_recorder_system(
+    mut recorder: ResMut<GhostRecorder>,
+    seed_config: Res<RunSeedConfig>,
+    mode_config: Res<GameModeConfig>,
+) {
+    *recorder = GhostRecorder {
+        seed: seed_config.fixed,
+        mode: mode_config.kind,
+        elapsed: Duration::ZERO,
+        timer: Timer::new(SAMPLE_INTERVAL, TimerMode::Repeating),
+        run: GhostRun::default(),
+        finished: false,
+    };
+}
+
+pub fn spawn_ghost_system(
+    mut commands: Commands,
+    seed_config: Res<RunSeedConfig>,
+    mode_config: Res<GameModeConfig>,
+    save: Res<SaveFile>,
+    atlases: Atlases,
+) {
+    let Some(seed) = seed_config.fixed else {
+        return;
+    };
+    let Some(run) = save.data().ghosts.runs.get(&(mode_config.kind, seed)) else {
+        return;
+    };
+    let Some(manifest) = atlases.get(SHADOW_ATLAS) else {
+        return;
+    };
+    let Some((texture_atlas, index)) = atlases.sprite(SHADOW_ATLAS, "idle") else {
+        return;
+    };
+    commands.spawn((
+        Ghost {
+            run: run.clone(),
+            last_scale: 1.,
+        },
+        Animator::player(manifest),
+        SpriteSheetBundle {
+            texture_atlas,
+            sprite: TextureAtlasSprite {
+                index,
+                color: Color::WHITE.with_a(GHOST_ALPHA),
+                custom_size: Some(Vec2::new(92., 92.)),
+                ..default()
+            },
+            transform: Transform::from_translation(Vec3::new(0., 0., 0.5)),
+            ..default()
+        },
+    ));
+}
+
+pub fn record_ghost_system(
+    time: Res<Time>,
+    mut recorder: ResMut<GhostRecorder>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    if recorder.seed.is_none() || recorder.finished {
+        return;
+    }
+    recorder.elapsed += time.delta();
+    recorder.timer.tick(time.delta());
+    if !recorder.timer.just_finished() && !recorder.run.samples.is_empty() {
+        return;
+    }
+    let time = recorder.elapsed.as_secs_f32();
+    for transform in player_query.iter() {
+        recorder.run.samples.push(GhostSample {
+            time,
+            x: transform.translation.x,
+            y: transform.translation.y,
+            scale: transform.scale.x,
+        });
+    }
+    recorder.run.duration = time;
+}
+
+pub fn ghost_playback_system(
+    recorder: Res<GhostRecorder>,
+    mut query: Query<(&mut Ghost, &mut Transform, &mut Visibility, &mut Animator)>,
+) {
+    let time = recorder.elapsed.as_secs_f32();
+    for (mut ghost, mut transform, mut visibility, mut animator) in query.iter_mut() {
+        let Some(sample) = ghost.run.sample_at(time) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+        transform.translation = Vec3::new(sample.x, sample.y, transform.translation.z);
+        transform.scale = Vec3::new(sample.scale, sample.scale, 1.);
+        let state = if sample.scale > ghost.last_scale {
+            AnimationState::Growing
+        } else if sample.scale < ghost.last_scale {
+            AnimationState::Shrinking
+        } else {
+            AnimationState::Idle
+        };
+        animator.play(state.name());
+        ghost.last_scale = sample.scale;
+    }
+}
+
+pub fn store_ghost_system(
+    mut recorder: ResMut<GhostRecorder>,
+    mut save: ResMut<SaveFile>,
+    mut events: EventReader<GameEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    score_query: Query<&TimeScore>,
+) {
+    let game_over = events
+        .read()
+        .filter(|event| matches!(event, GameEvent::GameOver))
+        .count();
+    if game_over == 0 || recorder.finished {
+        return;
+    }
+    recorder.finished = true;
+    let Some(seed) = recorder.seed else {
+        return;
+    };
+    let Some(score) = ModeScore::best(
+        score_query
+            .iter()
+            .map(|score| ModeScore::of(mode.as_deref(), score)),
+    ) else {
+        return;
+    };
+    recorder.run.score = score;
+    let key = (recorder.mode, seed);
+    let best = save.data().ghosts.runs.get(&key).map(|run| run.score);
+    if best.map_or(true, |best| score.beats(&best)) {
+        save.data_mut()
+            .ghosts
+            .runs
+            .insert(key, std::mem::take(&mut recorder.run));
+    }
+}
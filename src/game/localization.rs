@@ -0,0 +1,215 @@
+use bevy::{asset::LoadState, ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{
+    console::ConsoleAppExt,
+    ron_asset::{RonAsset, RonAssetAppExt},
+    settings::Settings,
+    *,
+};
+
+pub const FALLBACK_LANGUAGE: &str = "en";
+const LANGUAGES: [&str; 2] = ["en", "pt"];
+
+#[cfg(not(target_arch = "wasm32"))]
+fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen(
+    inline_js = "export function navigator_language() { return navigator.language || ''; }"
+)]
+extern "C" {
+    fn navigator_language() -> String;
+}
+
+#[cfg(target_arch = "wasm32")]
+fn system_language() -> Option<String> {
+    Some(navigator_language())
+}
+
+fn supported_language(locale: &str) -> Option<&'static str> {
+    let language = locale
+        .split(|c: char| c == '_' || c == '-' || c == '.')
+        .next()?
+        .to_lowercase();
+    LANGUAGES.into_iter().find(|it| *it == language)
+}
+
+pub fn resolve_language(settings: &Settings) -> &'static str {
+    settings
+        .language
+        .as_deref()
+        .and_then(supported_language)
+        .or_else(|| system_language().as_deref().and_then(supported_language))
+        .unwrap_or(FALLBACK_LANGUAGE)
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct StringTable {
+    pub strings: HashMap<String, String>,
+}
+
+impl RonAsset for StringTable {
+    const EXTENSIONS: &'static [&'static str] = &["strings.ron"];
+}
+
+#[derive(Resource, Default)]
+pub struct StringTables {
+    tables: HashMap<&'static str, Handle<StringTable>>,
+}
+
+#[derive(Resource)]
+pub struct Locale {
+    pub language: &'static str,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            language: FALLBACK_LANGUAGE,
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    locale: Res<'w, Locale>,
+    tables: Res<'w, StringTables>,
+    assets: Res<'w, Assets<StringTable>>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl<'w> Localizer<'w> {
+    fn lookup(&self, language: &str, key: &str) -> Option<&str> {
+        let handle = self.tables.tables.get(language)?;
+        self.assets
+            .get(handle)?
+            .strings
+            .get(key)
+            .map(String::as_str)
+    }
+
+    pub fn try_get(&self, key: &str) -> Option<&str> {
+        self.lookup(self.locale.language, key)
+            .or_else(|| self.lookup(FALLBACK_LANGUAGE, key))
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.try_get(key).unwrap_or(key).to_string()
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.get(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.locale.is_changed()
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.tables.tables.values().all(|handle| {
+            self.assets.contains(handle)
+                || self.asset_server.get_load_state(handle) == Some(LoadState::Failed)
+        })
+    }
+
+    pub fn failed(&self) -> Vec<&str> {
+        self.tables
+            .tables
+            .iter()
+            .filter(|(_, handle)| {
+                self.asset_server.get_load_state(*handle) == Some(LoadState::Failed)
+            })
+            .map(|(language, _)| *language)
+            .collect()
+    }
+}
+
+#[derive(Component)]
+pub struct LocalizedText {
+    pub key: &'static str,
+}
+
+pub struct LocalizationPlugin {}
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<StringTable>()
+            .init_resource::<StringTables>()
+            .init_resource::<Locale>()
+            .add_console_command("language", "language <en|pt|system>", language_command)
+            .add_systems(Startup, load_string_tables_system)
+            .add_systems(
+                Update,
+                (
+                    select_locale_system.run_if(resource_changed::<Settings>()),
+                    localized_text_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+impl Default for LocalizationPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn language_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let language = match args.first() {
+        Some(&"system") => None,
+        Some(code) => Some(
+            supported_language(code)
+                .ok_or_else(|| format!("unsupported language '{}'", code))?
+                .to_string(),
+        ),
+        None => {
+            return Ok(format!(
+                "language is {}",
+                world.resource::<Locale>().language
+            ))
+        }
+    };
+    world.resource_mut::<Settings>().language = language;
+    Ok(String::new())
+}
+
+pub fn load_string_tables_system(mut tables: ResMut<StringTables>, asset_server: Res<AssetServer>) {
+    for language in LANGUAGES {
+        tables.tables.insert(
+            language,
+            asset_server.load(format!("locales/{}.strings.ron", language)),
+        );
+    }
+}
+
+pub fn select_locale_system(settings: Res<Settings>, mut locale: ResMut<Locale>) {
+    let language = resolve_language(&settings);
+    if locale.language != language {
+        locale.language = language;
+    }
+}
+
+pub fn localized_text_system(
+    localizer: Localizer,
+    mut query: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let refresh = localizer.is_changed() || localizer.assets.is_changed();
+    for (localized, mut text) in query.iter_mut() {
+        if !refresh && !localized.is_changed() {
+            continue;
+        }
+        if let Some(section) = text.sections.first_mut() {
+            section.value = localizer.get(localized.key);
+        }
+    }
+}
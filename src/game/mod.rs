@@ -8,24 +8,68 @@ use bevy_parallax::{
 use bevy_rapier2d::prelude::*;
 use bevy_turborand::{prelude::*, DelegatedRng};
 use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::{f32::consts::PI, time::Duration};
 
+pub mod absorption;
+pub mod accessibility;
+pub mod achievements;
+pub mod animation;
+pub mod arena;
+pub mod atlas;
+pub mod audio;
+pub mod console;
+pub mod daily;
+pub mod debug_overlay;
+pub mod game_mode;
+pub mod gamepad;
+pub mod gate;
+pub mod ghost;
+pub mod localization;
+pub mod movement;
+pub mod particles;
+pub mod pause_menu;
+pub mod ron_asset;
+pub mod save;
+pub mod scale_control;
+pub mod settings;
+pub mod snapshot;
+pub mod steering;
+pub mod telemetry;
+pub mod terrain;
+pub mod touch;
+pub mod tutorial;
+
 const ORIGINAL_RADIUS: f32 = 32.;
+const MIN_SCALE: f32 = 0.1;
+const MAX_SCALE: f32 = 20.;
+const TRIGGER_PRESS: f32 = 0.2;
+const TRIGGER_RELEASE: f32 = 0.1;
 pub struct GamePlugins;
 
+#[derive(Resource, Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum PlayField {
+    #[default]
+    Scrolling,
+    Arena,
+}
+
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum GameState {
     #[default]
+    Loading,
     Startup,
     Running,
     Pause,
     Over,
+    Menu,
 }
 
 #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
 pub enum PlayerAction {
     Move,
     SwapScale,
+    Grow,
     Pause,
     Start,
 }
@@ -52,27 +96,40 @@ fn insert_gamepad(input_map: &mut InputMap<PlayerAction>) {
     input_map.insert(DualAxis::left_stick(), PlayerAction::Move);
     input_map.insert_multiple([
         (GamepadButtonType::South, PlayerAction::SwapScale),
+        (GamepadButtonType::RightTrigger2, PlayerAction::Grow),
         (GamepadButtonType::Start, PlayerAction::Pause),
         (GamepadButtonType::Start, PlayerAction::Start),
     ]);
 }
 
-fn create_input_map() -> InputMap<PlayerAction> {
+fn create_input_map(scheme: steering::ControlScheme) -> InputMap<PlayerAction> {
     let mut input_map = InputMap::default();
-    input_map.insert(left_keyboard_dap(), PlayerAction::Move);
-    input_map.insert(right_keyboard_dap(), PlayerAction::Move);
+    match scheme {
+        steering::ControlScheme::Standard => {
+            input_map.insert(left_keyboard_dap(), PlayerAction::Move);
+            input_map.insert(right_keyboard_dap(), PlayerAction::Move);
+        }
+        steering::ControlScheme::Mouse => {
+            input_map.insert(MouseButton::Left, PlayerAction::SwapScale);
+        }
+    }
     input_map.insert(KeyCode::Space, PlayerAction::SwapScale);
     input_map.insert(KeyCode::Escape, PlayerAction::Pause);
     input_map.insert(KeyCode::Escape, PlayerAction::Start);
     input_map.insert(KeyCode::Return, PlayerAction::Pause);
     input_map.insert(KeyCode::Return, PlayerAction::Start);
-    insert_gamepad(&mut input_map);
     input_map
 }
 
-fn create_input_manager() -> InputManagerBundle<PlayerAction> {
-    let mut input_map = create_input_map();
-    input_map.set_gamepad(Gamepad { id: 0 });
+fn create_input_manager(
+    scheme: steering::ControlScheme,
+    gamepad: Option<Gamepad>,
+) -> InputManagerBundle<PlayerAction> {
+    let mut input_map = create_input_map(scheme);
+    if let Some(gamepad) = gamepad {
+        insert_gamepad(&mut input_map);
+        input_map.set_gamepad(gamepad);
+    }
     InputManagerBundle {
         action_state: ActionState::default(),
         input_map: input_map,
@@ -85,6 +142,33 @@ impl PluginGroup for GamePlugins {
         group = group
             .add(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(1.0))
             .add(GamePlugin::default())
+            .add(arena::ArenaPlugin::default())
+            .add(terrain::TerrainPlugin::default())
+            .add(gate::GatePlugin::default())
+            .add(particles::ParticlePlugin::default())
+            .add(audio::GameAudioPlugin::default())
+            .add(animation::AnimationPlugin::default())
+            .add(atlas::AtlasPlugin::default())
+            .add(tutorial::TutorialPlugin::default())
+            .add(save::SavePlugin::default())
+            .add(settings::SettingsPlugin::default())
+            .add(localization::LocalizationPlugin::default())
+            .add(touch::TouchPlugin::default())
+            .add(steering::SteeringPlugin::default())
+            .add(scale_control::ScaleControlPlugin::default())
+            .add(movement::MovementPlugin::default())
+            .add(absorption::AbsorptionPlugin::default())
+            .add(game_mode::GameModePlugin::default())
+            .add(gamepad::GamepadPlugin::default())
+            .add(pause_menu::PauseMenuPlugin::default())
+            .add(snapshot::SnapshotPlugin::default())
+            .add(achievements::AchievementPlugin::default())
+            .add(daily::DailyPlugin::default())
+            .add(ghost::GhostPlugin::default())
+            .add(telemetry::TelemetryPlugin::default())
+            .add(console::ConsolePlugin::default())
+            .add(debug_overlay::DebugOverlayPlugin::default())
+            .add(accessibility::AccessibilityPlugin::default())
             .add(InputManagerPlugin::<PlayerAction>::default())
             .add(ParallaxPlugin)
             .add(RngPlugin::default());
@@ -96,59 +180,46 @@ impl PluginGroup for GamePlugins {
     }
 }
 
-pub enum SpriteUpdateStrategy {
-    Linear,
-    Boomerang(bool),
-}
-
-impl SpriteUpdateStrategy {
-    pub fn next(&mut self, current: usize, total: usize) -> usize {
-        match self {
-            Self::Linear => (current + 1) % total,
-            Self::Boomerang(dir) => {
-                let result = if *dir {
-                    current + 1 % total
-                } else {
-                    current - 1 % total
-                };
-                if result == 0 || result == total -1{
-                    *dir = !*dir;
-                }
-                result
-            }
-        }
-    }
-}
-#[derive(Component)]
-pub struct SpriteUpdate {
-    pub total: usize,
-    pub timer: Timer,
-    pub strategy: SpriteUpdateStrategy,
-}
-
-impl SpriteUpdate {
-    fn tick_and_next(&mut self, delta: Duration, current: usize) -> usize {
-        self.timer.tick(delta);
-        if self.timer.just_finished() {
-            self.strategy.next(current, self.total)
-        } else {
-            current
-        }
-    }
-}
-
 #[derive(Event)]
 pub enum GameEvent {
     Start,
     GameOver,
 }
 
+#[derive(Event, Debug, Clone, Copy)]
+pub enum GameplayEvent {
+    Absorbed {
+        kind: ObstacleKind,
+        position: Vec3,
+        scale: f32,
+    },
+    EffectApplied {
+        kind: ObstacleKind,
+    },
+    ScaleSwapped {
+        speed: f32,
+    },
+    Paused,
+    Resumed,
+    Died {
+        cause: DeathCause,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum DeathCause {
+    LeftView,
+    Eaten(ObstacleKind),
+    Poisoned,
+    Gate,
+}
+
 #[derive(Component)]
 pub struct Temporary {
     timer: Timer,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ObstacleKind {
     ScaleBust(bool),
     Block,
@@ -157,18 +228,40 @@ pub enum ObstacleKind {
 }
 
 impl ObstacleKind {
-    pub fn into_sprite_index(&self) -> usize {
+    pub fn random(random: &mut RngComponent) -> Self {
+        match random.u8(0..=4) {
+            0 => Self::ScaleBust(true),
+            1 => Self::ScaleBust(false),
+            2 => Self::Block,
+            3 => Self::Ice,
+            4 => Self::Poison,
+            _ => Self::Block,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "grow" => Some(Self::ScaleBust(true)),
+            "shrink" => Some(Self::ScaleBust(false)),
+            "block" => Some(Self::Block),
+            "ice" => Some(Self::Ice),
+            "poison" => Some(Self::Poison),
+            _ => None,
+        }
+    }
+
+    pub fn sprite_name(&self) -> &'static str {
         match self {
             Self::ScaleBust(dir) => {
                 if *dir {
-                    6
+                    "grow"
                 } else {
-                    2
+                    "shrink"
                 }
             }
-            Self::Block => 7,
-            Self::Ice => 4,
-            Self::Poison => 1,
+            Self::Block => "block",
+            Self::Ice => "ice",
+            Self::Poison => "poison",
         }
     }
 
@@ -229,6 +322,9 @@ pub struct Obstacle {
     kind: ObstacleKind,
 }
 
+#[derive(Component)]
+pub struct Wall {}
+
 impl Obstacle {
     pub fn create_effect(&self, commands: &mut Commands, target: Entity, scale: &Scale) {
         match self.kind {
@@ -270,8 +366,8 @@ impl BustEffect {
     pub fn apply(&self, delta: Duration, transform: &mut Transform) {
         let mut new_scale = transform.scale * 1. + (self.speed * delta.as_secs_f32());
         new_scale = Vec3::new(
-            f32::min(f32::max(new_scale.x, 0.1), 20.),
-            f32::min(f32::max(new_scale.y, 0.1), 20.),
+            f32::min(f32::max(new_scale.x, MIN_SCALE), MAX_SCALE),
+            f32::min(f32::max(new_scale.y, MIN_SCALE), MAX_SCALE),
             1.,
         );
         transform.translation.z = new_scale.length();
@@ -295,6 +391,10 @@ pub fn calc_speed(transform: &Transform) -> f32 {
 #[derive(Component)]
 pub struct Player {}
 
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlayerSlot(pub usize);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Strategy {
     None,
     Follow { max_distance: f32 },
@@ -323,7 +423,7 @@ impl Strategy {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct Enemy {
     when_bigger: Strategy,
     when_smaller: Strategy,
@@ -381,25 +481,30 @@ impl Enemy {
         }
     }
 
-    pub fn tick(
-        &self,
-        enemy: (&Transform, &Velocity),
-        player: (&Transform, &Velocity),
-    ) -> Velocity {
-        let enemy_length = enemy.0.scale.length();
-        let player_length = player.0.scale.length();
-        let player_radius = player.0.scale.length() * ORIGINAL_RADIUS;
-        let diff = player.0.translation.truncate() - enemy.0.translation.truncate();
+    pub fn choose(&self, enemy: &Transform, player: &Transform) -> (&Strategy, Vec2) {
+        let enemy_length = enemy.scale.length();
+        let player_length = player.scale.length();
+        let player_radius = player.scale.length() * ORIGINAL_RADIUS;
+        let diff = player.translation.truncate() - enemy.translation.truncate();
         let direction = diff.normalize_or_zero();
         let distance = f32::max(diff.length() - player_radius, 0.);
 
-        let enemy_target_direction = if enemy_length > player_length {
-            self.when_bigger.calc(direction, distance)
+        let strategy = if enemy_length > player_length {
+            &self.when_bigger
         } else if player_length > enemy_length {
-            self.when_smaller.calc(direction, distance)
+            &self.when_smaller
         } else {
-            self.when_equal.calc(direction, distance)
+            &self.when_equal
         };
+        (strategy, strategy.calc(direction, distance))
+    }
+
+    pub fn tick(
+        &self,
+        enemy: (&Transform, &Velocity),
+        player: (&Transform, &Velocity),
+    ) -> Velocity {
+        let (_, enemy_target_direction) = self.choose(enemy.0, player.0);
 
         let target_lin_velocity = enemy_target_direction * calc_speed(enemy.0);
         let final_lin_dir =
@@ -438,28 +543,66 @@ impl TimeScore {
         self.elapsed_time += delta;
     }
 
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_time
+    }
+
+    pub fn add_bonus(&mut self, bonus: Duration) {
+        self.elapsed_time += bonus;
+    }
+
     pub fn to_string(&self) -> String {
-        let minutes = self.elapsed_time.as_secs() / 60;
-        let seconds = self.elapsed_time.as_secs() % 60;
-        format!("{:02}:{:02}", minutes, seconds)
+        format_time(self.elapsed_time)
     }
 }
 
+pub fn format_time(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let seconds = duration.as_secs() % 60;
+    format!("{:02}:{:02}", minutes, seconds)
+}
+
 #[derive(Component)]
 pub struct Scale {
     speed: f32,
+    rate: f32,
 }
 
 impl Scale {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            speed,
+            rate: speed.abs(),
+        }
+    }
+
     pub fn swap(&mut self) {
         self.speed = -self.speed;
     }
 
+    pub fn hold(&mut self, pressed: bool) {
+        self.speed = if pressed { self.rate } else { -self.rate };
+    }
+
+    pub fn throttle(&mut self, value: f32) {
+        let value = value.clamp(0., 1.);
+        let threshold = if self.speed > 0. {
+            TRIGGER_RELEASE
+        } else {
+            TRIGGER_PRESS
+        };
+        self.speed = if value > threshold {
+            self.rate * value
+        } else {
+            -self.rate
+        };
+    }
+
     pub fn apply(&self, delta: Duration, transform: &mut Transform) {
         let mut new_scale = transform.scale * 1. + (self.speed * delta.as_secs_f32());
         new_scale = Vec3::new(
-            f32::min(f32::max(new_scale.x, 0.1), 20.),
-            f32::min(f32::max(new_scale.y, 0.1), 20.),
+            f32::min(f32::max(new_scale.x, MIN_SCALE), MAX_SCALE),
+            f32::min(f32::max(new_scale.y, MIN_SCALE), MAX_SCALE),
             1.,
         );
         transform.translation.z = new_scale.length();
@@ -467,6 +610,17 @@ impl Scale {
     }
 }
 
+#[derive(Resource, Default)]
+pub struct RunSeedConfig {
+    pub fixed: Option<u64>,
+}
+
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct RunSeed(pub u64);
+
+#[derive(Resource, Default)]
+pub struct GodMode(pub bool);
+
 impl Default for Player {
     fn default() -> Self {
         Self {}
@@ -479,7 +633,11 @@ impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnObstacleEvent>()
             .add_event::<GameEvent>()
+            .add_event::<GameplayEvent>()
             .add_state::<GameState>()
+            .init_resource::<PlayField>()
+            .init_resource::<RunSeedConfig>()
+            .init_resource::<GodMode>()
             .add_systems(Startup, spawn_camera_system)
             .add_systems(
                 OnEnter(GameState::Startup),
@@ -489,10 +647,6 @@ impl Plugin for GamePlugin {
                 Update,
                 player_pause_system.run_if(in_state(GameState::Running)),
             )
-            .add_systems(
-                Update,
-                player_unpause_system.run_if(in_state(GameState::Pause)),
-            )
             .add_systems(
                 Update,
                 player_restart_system.run_if(in_state(GameState::Over)),
@@ -500,19 +654,18 @@ impl Plugin for GamePlugin {
             .add_systems(
                 Update,
                 (
-                    player_move_system,
-                    player_swap_scale_system,
+                    player_move_system.run_if(movement::is_direct),
+                    scale_control::player_scale_control_system,
                     apply_scale_system,
-                    obstacle_factory_system,
+                    obstacle_factory_system.run_if(not(resource_exists::<tutorial::Tutorial>())),
                     spawn_obstacle_system,
-                    despawn_out_of_view,
+                    despawn_out_of_view.run_if(resource_equals(PlayField::Scrolling)),
                     hit_obstacle_system,
                     bust_effect_system,
                     temporary_despawn_system,
                     time_score_system,
                     destroy_system,
                     enemy_system,
-                    sprite_update_system,
                     move_camera_system.before(ParallaxSystems),
                 )
                     .run_if(in_state(GameState::Running)),
@@ -552,8 +705,10 @@ pub fn reset_camera_system(
 pub fn spawn_world(
     mut commands: Commands,
     mut global_rng: ResMut<GlobalRng>,
+    seed_config: Res<RunSeedConfig>,
     mut state: ResMut<NextState<GameState>>,
     mut time: ResMut<Time<Virtual>>,
+    localizer: localization::Localizer,
 ) {
     time.unpause();
     state.set(GameState::Running);
@@ -561,16 +716,18 @@ pub fn spawn_world(
         gravity: Vec2::ZERO,
         ..default()
     });
+    let seed = seed_config.fixed.unwrap_or_else(|| global_rng.u64(..));
+    commands.insert_resource(RunSeed(seed));
     commands.spawn((
         ObstacleFactoryComponent {
             timer: Timer::new(Duration::from_secs(1), TimerMode::Repeating),
         },
-        RngComponent::from(&mut global_rng),
+        RngComponent::with_seed(seed),
     ));
     commands
         .spawn(
             TextBundle::from_section(
-                "??:??",
+                localizer.get("score.placeholder"),
                 TextStyle {
                     font_size: 64.,
                     ..default()
@@ -639,6 +796,7 @@ impl ObstacleFactoryComponent {
         random: &mut RngComponent,
         camera_info: (&Transform, &Velocity),
         player_info: (&Transform),
+        policy: &game_mode::SpawnPolicy,
         event: &mut EventWriter<SpawnObstacleEvent>,
     ) {
         if !self.timer.just_finished() {
@@ -649,18 +807,11 @@ impl ObstacleFactoryComponent {
         let obstacle_direction = camera_direction.rotate(Vec2::from_angle(PI / 2.));
         let obstacle_middle =
             camera_transform.translation.truncate() + (camera_direction * 1080. / 2. + 64.);
-        for _ in 0..2 {
+        for _ in 0..policy.per_wave {
             let scale = 0.75 + random.f32() * 0.50;
             let position =
                 obstacle_middle + obstacle_direction * random.f32_normalized() * 720. / 2.;
-            let kind = match random.u8(0..=4) {
-                0 => ObstacleKind::ScaleBust(true),
-                1 => ObstacleKind::ScaleBust(false),
-                2 => ObstacleKind::Block,
-                3 => ObstacleKind::Ice,
-                4 => ObstacleKind::Poison,
-                _ => ObstacleKind::Block,
-            };
+            let kind = policy.kind(random);
             event.send(SpawnObstacleEvent {
                 color: kind.get_color(),
                 position: position.extend(0.),
@@ -683,6 +834,8 @@ pub struct SpawnObstacleEvent {
 
 pub fn obstacle_factory_system(
     time: Res<Time>,
+    play_field: Res<PlayField>,
+    mode: Res<game_mode::ActiveGameMode>,
     mut query: Query<(&mut ObstacleFactoryComponent, &mut RngComponent)>,
     mut events: EventWriter<SpawnObstacleEvent>,
     camera_query: Query<(&Transform, &Velocity), With<Camera>>,
@@ -690,9 +843,15 @@ pub fn obstacle_factory_system(
 ) {
     if let Ok(camera_info) = camera_query.get_single() {
         if let Ok(player_info) = player_query.get_single() {
+            let policy = mode.mode.spawn_policy();
             for (mut factory, mut random) in query.iter_mut() {
                 factory.tick(time.delta());
-                factory.create(&mut random, camera_info, player_info, &mut events);
+                match *play_field {
+                    PlayField::Scrolling => {
+                        factory.create(&mut random, camera_info, player_info, &policy, &mut events)
+                    }
+                    PlayField::Arena => factory.create_in_arena(&mut random, &policy, &mut events),
+                }
             }
         }
     }
@@ -701,73 +860,76 @@ pub fn obstacle_factory_system(
 pub fn spawn_obstacle_system(
     mut commands: Commands,
     mut events: EventReader<SpawnObstacleEvent>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    atlases: atlas::Atlases,
 ) {
-    let handler: Handle<Image> = asset_server.load("marbles.png");
-    let texture_atlas_handle = texture_atlases.add(TextureAtlas::from_grid(
-        handler,
-        Vec2::new(672., 672.),
-        3,
-        3,
-        Some(Vec2::new(216., 170.)),
-        None,
-    ));
     for event in events.read() {
-        let scale = Vec3::new(event.scale, event.scale, 1.);
-        let z = scale.length();
-        let translation = event.position.truncate().extend(z);
-        let transform = Transform::from_translation(translation).with_scale(Vec3::new(
-            event.scale,
-            event.scale,
-            1.,
-        ));
-        let mut obstacle_commands = commands.spawn(Obstacle { kind: event.kind });
-        obstacle_commands
-            .insert(Collider::ball(event.radius))
-            .insert(Sleeping::disabled())
-            //.insert(CollidingEntities::default())
-            //.insert(Sensor::default())
-            .insert(ActiveEvents::all())
-            //.insert(ActiveHooks::all())
-            .insert(SpriteSheetBundle {
-                texture_atlas: texture_atlas_handle.clone(),
-                sprite: TextureAtlasSprite {
-                    index: event.kind.into_sprite_index(),
-                    custom_size: Some(Vec2::new(event.radius * 2., event.radius * 2.)),
-                    ..default()
-                },
-                transform,
-                ..default()
-            });
-        event.kind.add_bundle(&mut obstacle_commands);
+        spawn_obstacle(&mut commands, &atlases, event);
     }
 }
 
+pub fn spawn_obstacle(
+    commands: &mut Commands,
+    atlases: &atlas::Atlases,
+    event: &SpawnObstacleEvent,
+) -> Option<Entity> {
+    let (texture_atlas_handle, sprite_index) =
+        atlases.sprite(atlas::MARBLES_ATLAS, event.kind.sprite_name())?;
+    let scale = Vec3::new(event.scale, event.scale, 1.);
+    let z = scale.length();
+    let translation = event.position.truncate().extend(z);
+    let transform = Transform::from_translation(translation).with_scale(Vec3::new(
+        event.scale,
+        event.scale,
+        1.,
+    ));
+    let mut obstacle_commands = commands.spawn(Obstacle { kind: event.kind });
+    obstacle_commands
+        .insert(Collider::ball(event.radius))
+        .insert(Sleeping::disabled())
+        //.insert(CollidingEntities::default())
+        //.insert(Sensor::default())
+        .insert(ActiveEvents::all())
+        //.insert(ActiveHooks::all())
+        .insert(animation::Animator::obstacle(sprite_index))
+        .insert(SpriteSheetBundle {
+            texture_atlas: texture_atlas_handle,
+            sprite: TextureAtlasSprite {
+                index: sprite_index,
+                custom_size: Some(Vec2::new(event.radius * 2., event.radius * 2.)),
+                ..default()
+            },
+            transform,
+            ..default()
+        });
+    event.kind.add_bundle(&mut obstacle_commands);
+    Some(obstacle_commands.id())
+}
+
 pub fn spawn_player_system(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    atlases: atlas::Atlases,
+    settings: Res<settings::Settings>,
+    gamepads: Res<Gamepads>,
 ) {
-    let handler: Handle<Image> = asset_server.load("shadow-sprite.png");
-    let texture_atlas_handle = texture_atlases.add(TextureAtlas::from_grid(
-        handler,
-        Vec2::new(705., 705.),
-        1,
-        4,
-        None,
-        None,
-    ));
+    let Some(manifest) = atlases.get(atlas::SHADOW_ATLAS) else {
+        return;
+    };
+    let Some((texture_atlas_handle, sprite_index)) = atlases.sprite(atlas::SHADOW_ATLAS, "idle")
+    else {
+        return;
+    };
     let initial_scale_speed = 0.5;
     let initial_size = ORIGINAL_RADIUS;
     commands
         .spawn(Player::default())
-        .insert(create_input_manager())
-        .insert(Scale {
-            speed: initial_scale_speed,
-        })
+        .insert(PlayerSlot(0))
+        .insert(create_input_manager(
+            settings.control_scheme,
+            gamepad::player_gamepad(&settings, &gamepads, PlayerSlot(0)),
+        ))
+        .insert(Scale::new(initial_scale_speed))
         .insert(Collider::ball(initial_size))
         .insert(Sleeping::disabled())
         .insert(Ccd::enabled())
@@ -780,14 +942,11 @@ pub fn spawn_player_system(
             Group::from_bits_retain(0b1),
             Group::from_bits_retain(0b1),
         ))
-        .insert(SpriteUpdate {
-            total: 4,
-            timer: Timer::new(Duration::from_millis(200), TimerMode::Repeating),
-            strategy: SpriteUpdateStrategy::Boomerang(true),
-        })
+        .insert(animation::Animator::player(manifest))
         .insert(SpriteSheetBundle {
-            texture_atlas: texture_atlas_handle.clone(),
+            texture_atlas: texture_atlas_handle,
             sprite: TextureAtlasSprite {
+                index: sprite_index,
                 custom_size: Some(Vec2::new(92., 92.)),
                 ..default()
             },
@@ -819,27 +978,14 @@ pub fn player_pause_system(
     mut time: ResMut<Time<Virtual>>,
     mut state: ResMut<NextState<GameState>>,
     query: Query<(Entity, &ActionState<PlayerAction>), With<Player>>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
     for (_, action_state) in query.iter() {
         if action_state.just_pressed(PlayerAction::Pause) {
             if !time.is_paused() {
                 state.set(GameState::Pause);
                 time.pause();
-            }
-        }
-    }
-}
-
-pub fn player_unpause_system(
-    mut time: ResMut<Time<Virtual>>,
-    mut state: ResMut<NextState<GameState>>,
-    query: Query<(Entity, &ActionState<PlayerAction>), With<Player>>,
-) {
-    for (_, action_state) in query.iter() {
-        if action_state.just_pressed(PlayerAction::Pause) {
-            if time.is_paused() {
-                state.set(GameState::Running);
-                time.unpause();
+                gameplay_events.send(GameplayEvent::Paused);
             }
         }
     }
@@ -861,16 +1007,6 @@ pub fn player_restart_system(
     }
 }
 
-pub fn player_swap_scale_system(
-    mut query: Query<(&mut Scale, &ActionState<PlayerAction>), With<Player>>,
-) {
-    for (mut scale, action_state) in query.iter_mut() {
-        if action_state.just_pressed(PlayerAction::SwapScale) {
-            scale.swap();
-        }
-    }
-}
-
 pub fn apply_scale_system(
     time: Res<Time>,
     mut query: Query<(Entity, &Scale, &mut Transform)>,
@@ -892,7 +1028,8 @@ pub fn despawn_out_of_view(
         (Entity, &ViewVisibility, &Transform),
         (Without<LayerComponent>, Without<LayerTextureComponent>),
     >,
-    mut events: EventWriter<GameEvent>,
+    god_mode: Res<GodMode>,
+    mut losses: EventWriter<game_mode::LossEvent>,
 ) {
     let camera_info = camera_query.get_single().unwrap();
     let camera_position = camera_info.0.translation;
@@ -908,7 +1045,12 @@ pub fn despawn_out_of_view(
             .abs();
         if angle >= (90_f32).to_radians() && angle <= (270_f32).to_radians() {
             if let Ok(_) = is_player.get(entity) {
-                events.send(GameEvent::GameOver);
+                if god_mode.0 {
+                    continue;
+                }
+                losses.send(game_mode::LossEvent {
+                    cause: DeathCause::LeftView,
+                });
             } else {
                 commands.entity(entity).despawn_recursive();
             }
@@ -917,35 +1059,83 @@ pub fn despawn_out_of_view(
 }
 
 pub fn hit_obstacle_system(
-    rapier_context: Res<RapierContext>,
+    time: Res<Time>,
+    settings: Res<settings::Settings>,
     mut commands: Commands,
-    mut player_query: Query<(Entity, &CollidingEntities, &Scale, &Transform), With<Player>>,
-    obstacle_query: Query<(Entity, &Obstacle, &Transform)>,
-    mut events: EventWriter<GameEvent>,
+    mut player_query: Query<(Entity, &CollidingEntities, &Scale, &mut Transform), With<Player>>,
+    mut obstacle_query: Query<(Entity, &Obstacle, &mut Transform), Without<Player>>,
+    god_mode: Res<GodMode>,
+    mut losses: EventWriter<game_mode::LossEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
+    let partial = settings.absorption == absorption::AbsorptionMode::Partial;
     for player_info in player_query.iter_mut() {
-        let (player_entity, colliding_entities, scale, player_transform) = player_info;
-        let player_length = player_transform.scale.x;
+        let (player_entity, colliding_entities, scale, mut player_transform) = player_info;
         for colliding_entity in colliding_entities.iter() {
-            if let Ok(obstacle_info) = obstacle_query.get(colliding_entity) {
-                let (obstacle_entity, obstacle, obstacle_transform) = obstacle_info;
-                let obstacle_length = obstacle_transform.scale.x;
-                let intersection = rapier_context.contact_pair(colliding_entity, player_entity);
-                let contact_pair_view = intersection.unwrap();
-                let deepest_contact = contact_pair_view.find_deepest_contact().unwrap();
-                let penetration = deepest_contact.1.dist();
-                let normal = deepest_contact.0.normal();
-                if player_length >= obstacle_length {
-                    if penetration.abs() >= ORIGINAL_RADIUS * 2. * obstacle_length {
-                        obstacle.create_effect(&mut commands, player_entity, scale);
-                        commands.entity(obstacle_entity).despawn_recursive();
+            let Ok(obstacle_info) = obstacle_query.get_mut(colliding_entity) else {
+                continue;
+            };
+            let (obstacle_entity, obstacle, mut obstacle_transform) = obstacle_info;
+            let player_length = player_transform.scale.x;
+            let obstacle_length = obstacle_transform.scale.x;
+            let overlap = absorption::overlap(&player_transform, &obstacle_transform);
+            let (absorbed, eaten) =
+                if partial && absorption::is_near_equal(player_length, obstacle_length) {
+                    let area = absorption::transfer_area(
+                        player_length.min(obstacle_length),
+                        overlap,
+                        time.delta(),
+                    );
+                    if player_length >= obstacle_length {
+                        let remaining = absorption::add_area(obstacle_length, -area);
+                        absorption::resize(
+                            &mut player_transform,
+                            absorption::add_area(player_length, area),
+                        );
+                        absorption::resize(&mut obstacle_transform, remaining);
+                        (remaining <= MIN_SCALE, false)
+                    } else {
+                        let remaining = absorption::add_area(player_length, -area);
+                        absorption::resize(
+                            &mut obstacle_transform,
+                            absorption::add_area(obstacle_length, area),
+                        );
+                        absorption::resize(&mut player_transform, remaining);
+                        (false, remaining <= MIN_SCALE)
                     }
+                } else if player_length >= obstacle_length {
+                    (overlap >= 1., false)
                 } else {
-                    if penetration.abs() >= ORIGINAL_RADIUS * 2. * player_length {
-                        events.send(GameEvent::GameOver);
-                    }
-                }
+                    (false, overlap >= 1.)
+                };
+            if eaten && !god_mode.0 {
+                losses.send(game_mode::LossEvent {
+                    cause: DeathCause::Eaten(obstacle.kind),
+                });
+            }
+            if !absorbed {
+                continue;
             }
+            if partial {
+                let area = absorption::absorbed_area(obstacle_transform.scale.x);
+                let player_length = player_transform.scale.x;
+                absorption::resize(
+                    &mut player_transform,
+                    absorption::add_area(player_length, area),
+                );
+            }
+            obstacle.create_effect(&mut commands, player_entity, scale);
+            gameplay_events.send(GameplayEvent::Absorbed {
+                kind: obstacle.kind,
+                position: obstacle_transform.translation,
+                scale: obstacle_length,
+            });
+            if obstacle.kind != ObstacleKind::Block {
+                gameplay_events.send(GameplayEvent::EffectApplied {
+                    kind: obstacle.kind,
+                });
+            }
+            commands.entity(obstacle_entity).despawn_recursive();
         }
     }
 }
@@ -978,12 +1168,13 @@ pub fn temporary_despawn_system(
 pub fn time_score_system(
     mut commands: Commands,
     time: Res<Time>,
+    mode: Option<Res<game_mode::ActiveGameMode>>,
     mut score_query: Query<(Entity, &mut TimeScore)>,
 ) {
     for (entity, mut score) in score_query.iter_mut() {
         score.tick(time.delta());
         commands.entity(entity).insert(Text::from_section(
-            score.to_string(),
+            game_mode::ModeScore::of(mode.as_deref(), &score).to_string(),
             TextStyle {
                 font_size: 64.,
                 color: Color::DARK_GRAY,
@@ -998,15 +1189,21 @@ pub fn game_event_system(
     mut time: ResMut<Time<Virtual>>,
     mut events: EventReader<GameEvent>,
     mut state: ResMut<NextState<GameState>>,
+    mode: Option<Res<game_mode::ActiveGameMode>>,
 ) {
     for event in events.read() {
         match event {
             GameEvent::GameOver => {
                 time.pause();
                 state.set(GameState::Over);
-                commands.spawn(
+                let key = mode
+                    .as_ref()
+                    .map(|mode| mode.mode.over_key())
+                    .unwrap_or("game-over");
+                commands.spawn((
+                    localization::LocalizedText { key },
                     TextBundle::from_section(
-                        "Game Over",
+                        "",
                         TextStyle {
                             color: Color::DARK_GRAY,
                             font_size: 64.,
@@ -1020,7 +1217,7 @@ pub fn game_event_system(
                         width: Val::Percent(1.),
                         ..default()
                     }),
-                );
+                ));
             }
             GameEvent::Start => {
                 state.set(GameState::Startup);
@@ -1033,12 +1230,19 @@ pub fn destroy_system(
     mut commands: Commands,
     query: Query<(Entity, &Destroy)>,
     is_player: Query<(Entity), With<Player>>,
-    mut events: EventWriter<GameEvent>,
+    god_mode: Res<GodMode>,
+    mut losses: EventWriter<game_mode::LossEvent>,
 ) {
     for (destroy_entity, destroy) in query.iter() {
         let target = destroy.target;
         if let Ok(player) = is_player.get(target) {
-            events.send(GameEvent::GameOver);
+            if god_mode.0 {
+                commands.entity(destroy_entity).despawn();
+                continue;
+            }
+            losses.send(game_mode::LossEvent {
+                cause: DeathCause::Poisoned,
+            });
         } else {
             commands.entity(target).despawn();
         }
@@ -1060,12 +1264,3 @@ pub fn enemy_system(
         commands.entity(enemy).try_insert(velocity);
     }
 }
-
-pub fn sprite_update_system(
-    time: Res<Time>,
-    mut query: Query<(&mut SpriteUpdate, &mut TextureAtlasSprite)>,
-) {
-    for (mut sprite_update, mut sprite) in query.iter_mut() {
-        sprite.index = sprite_update.tick_and_next(time.delta(), sprite.index);
-    }
-}
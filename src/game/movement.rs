@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{console::ConsoleAppExt, settings::Settings, *};
+
+const BASE_MASS: f32 = 1.;
+const LINEAR_DAMPING: f32 = 4.;
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MovementMode {
+    #[default]
+    Direct,
+    Momentum,
+}
+
+impl MovementMode {
+    pub const ALL: [Self; 2] = [Self::Direct, Self::Momentum];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Direct => "direct",
+            Self::Momentum => "momentum",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|it| it == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+pub fn mass(transform: &Transform) -> f32 {
+    BASE_MASS * transform.scale.x * transform.scale.y
+}
+
+pub fn damping(transform: &Transform) -> f32 {
+    LINEAR_DAMPING / transform.scale.x
+}
+
+pub fn is_direct(settings: Res<Settings>) -> bool {
+    settings.movement == MovementMode::Direct
+}
+
+pub fn is_momentum(settings: Res<Settings>) -> bool {
+    settings.movement == MovementMode::Momentum
+}
+
+pub struct MovementPlugin {}
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command("movement", "movement <direct|momentum>", movement_command)
+            .add_systems(
+                Update,
+                apply_movement_mode_system.run_if(resource_changed::<Settings>()),
+            )
+            .add_systems(
+                Update,
+                (
+                    momentum_move_system,
+                    mass_system,
+                    momentum_contact_system.before(hit_obstacle_system),
+                )
+                    .run_if(in_state(GameState::Running))
+                    .run_if(is_momentum),
+            );
+    }
+}
+
+impl Default for MovementPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn movement_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing mode")?;
+    let mode = MovementMode::from_name(name).ok_or_else(|| format!("unknown mode '{}'", name))?;
+    world.resource_mut::<Settings>().movement = mode;
+    Ok(format!("movement set to {}", mode.name()))
+}
+
+pub fn apply_movement_mode_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    query: Query<Entity, Or<(With<Player>, With<Obstacle>)>>,
+) {
+    if settings.movement != MovementMode::Direct {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).remove::<(
+            ExternalForce,
+            ExternalImpulse,
+            Damping,
+            ColliderMassProperties,
+        )>();
+    }
+}
+
+pub fn momentum_move_system(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &ActionState<PlayerAction>,
+            &Transform,
+            Option<&Velocity>,
+        ),
+        With<Player>,
+    >,
+    frozen_query: Query<&FrozenEffect>,
+) {
+    for (entity, action_state, transform, velocity) in query.iter() {
+        if velocity.is_none() {
+            commands.entity(entity).insert(Velocity::zero());
+        }
+        if frozen_query.iter().any(|it| it.target == entity) {
+            commands
+                .entity(entity)
+                .insert((Velocity::zero(), ExternalForce::default()));
+            continue;
+        }
+        let direction = action_state
+            .axis_pair(PlayerAction::Move)
+            .map(|axis| axis.xy().clamp_length_max(1.))
+            .unwrap_or(Vec2::ZERO);
+        let damping = damping(transform);
+        commands.entity(entity).insert((
+            ExternalForce {
+                force: direction * calc_speed(transform) * mass(transform) * damping,
+                torque: 0.,
+            },
+            Damping {
+                linear_damping: damping,
+                angular_damping: LINEAR_DAMPING,
+            },
+        ));
+    }
+}
+
+pub fn mass_system(
+    mut commands: Commands,
+    query: Query<
+        (Entity, &Transform, Option<&ColliderMassProperties>),
+        Or<(With<Player>, With<Obstacle>)>,
+    >,
+) {
+    for (entity, transform, properties) in query.iter() {
+        let mass = mass(transform);
+        if let Some(ColliderMassProperties::Mass(current)) = properties {
+            if (current - mass).abs() < f32::EPSILON {
+                continue;
+            }
+        }
+        commands
+            .entity(entity)
+            .insert(ColliderMassProperties::Mass(mass));
+    }
+}
+
+pub fn momentum_contact_system(
+    mut commands: Commands,
+    player_query: Query<(Entity, &CollidingEntities, &Transform, &Velocity), With<Player>>,
+    obstacle_query: Query<(&Transform, &Velocity), (With<Obstacle>, Without<Player>)>,
+) {
+    for (player, colliding_entities, player_transform, player_velocity) in player_query.iter() {
+        let player_mass = mass(player_transform);
+        let mut recoil = Vec2::ZERO;
+        for entity in colliding_entities.iter() {
+            let Ok((transform, velocity)) = obstacle_query.get(entity) else {
+                continue;
+            };
+            let normal = (transform.translation - player_transform.translation)
+                .truncate()
+                .normalize_or_zero();
+            let closing = (player_velocity.linvel - velocity.linvel).dot(normal);
+            if closing <= 0. {
+                continue;
+            }
+            let obstacle_mass = mass(transform);
+            let impulse =
+                normal * closing * player_mass * obstacle_mass / (player_mass + obstacle_mass);
+            commands.entity(entity).try_insert(ExternalImpulse {
+                impulse,
+                torque_impulse: 0.,
+            });
+            recoil -= impulse;
+        }
+        if recoil != Vec2::ZERO {
+            commands.entity(player).insert(ExternalImpulse {
+                impulse: recoil,
+                torque_impulse: 0.,
+            });
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use bevy_turborand::{prelude::*, DelegatedRng};
+use std::{f32::consts::PI, time::Duration};
+
+use super::*;
+
+const PARTICLE_Z: f32 = 50.;
+
+#[derive(Component)]
+pub struct Particle {
+    velocity: Vec2,
+    lifetime: Timer,
+    start_color: Color,
+    end_color: Color,
+    start_size: f32,
+    end_size: f32,
+}
+
+impl Particle {
+    pub fn progress(&self) -> f32 {
+        self.lifetime.percent()
+    }
+
+    pub fn color(&self) -> Color {
+        let t = self.progress();
+        let start = self.start_color.as_rgba_f32();
+        let end = self.end_color.as_rgba_f32();
+        Color::rgba(
+            start[0] + (end[0] - start[0]) * t,
+            start[1] + (end[1] - start[1]) * t,
+            start[2] + (end[2] - start[2]) * t,
+            start[3] + (end[3] - start[3]) * t,
+        )
+    }
+
+    pub fn size(&self) -> f32 {
+        self.start_size + (self.end_size - self.start_size) * self.progress()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParticlePreset {
+    pub count: usize,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub lifetime: Duration,
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+impl ParticlePreset {
+    pub fn absorb(color: Color) -> Self {
+        Self {
+            count: 12,
+            min_speed: 40.,
+            max_speed: 120.,
+            lifetime: Duration::from_millis(400),
+            start_color: color,
+            end_color: color.with_a(0.),
+            start_size: 10.,
+            end_size: 2.,
+        }
+    }
+
+    pub fn freeze() -> Self {
+        Self {
+            count: 24,
+            min_speed: 20.,
+            max_speed: 60.,
+            lifetime: Duration::from_millis(800),
+            start_color: Color::rgb(0.8, 0.95, 1.),
+            end_color: Color::rgba(0.5, 0.8, 1., 0.),
+            start_size: 6.,
+            end_size: 12.,
+        }
+    }
+
+    pub fn bust_grow() -> Self {
+        Self {
+            count: 16,
+            min_speed: 80.,
+            max_speed: 160.,
+            lifetime: Duration::from_millis(500),
+            start_color: ObstacleKind::ScaleBust(true).get_color(),
+            end_color: Color::rgba(0., 0., 1., 0.),
+            start_size: 4.,
+            end_size: 14.,
+        }
+    }
+
+    pub fn bust_shrink() -> Self {
+        Self {
+            count: 16,
+            min_speed: -160.,
+            max_speed: -80.,
+            lifetime: Duration::from_millis(500),
+            start_color: ObstacleKind::ScaleBust(false).get_color(),
+            end_color: Color::rgba(1., 0., 0., 0.),
+            start_size: 14.,
+            end_size: 2.,
+        }
+    }
+
+    pub fn death() -> Self {
+        Self {
+            count: 48,
+            min_speed: 60.,
+            max_speed: 320.,
+            lifetime: Duration::from_millis(1200),
+            start_color: Color::DARK_GRAY,
+            end_color: Color::rgba(0., 0., 0., 0.),
+            start_size: 16.,
+            end_size: 4.,
+        }
+    }
+
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        random: &mut GlobalRng,
+        origin: Vec2,
+        radius: f32,
+    ) {
+        for _ in 0..self.count {
+            let direction = Vec2::from_angle(random.f32() * PI * 2.);
+            let speed = self.min_speed + random.f32() * (self.max_speed - self.min_speed);
+            let position = origin + direction * radius;
+            commands.spawn((
+                Particle {
+                    velocity: direction * speed,
+                    lifetime: Timer::new(self.lifetime, TimerMode::Once),
+                    start_color: self.start_color,
+                    end_color: self.end_color,
+                    start_size: self.start_size,
+                    end_size: self.end_size,
+                },
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: self.start_color,
+                        custom_size: Some(Vec2::splat(self.start_size)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(position.extend(PARTICLE_Z)),
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+pub struct ParticlePlugin {}
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                absorb_particles_system,
+                death_particles_system,
+                particle_update_system,
+            ),
+        );
+    }
+}
+
+impl Default for ParticlePlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn absorb_particles_system(
+    mut commands: Commands,
+    mut random: ResMut<GlobalRng>,
+    settings: Res<settings::Settings>,
+    mut events: EventReader<GameplayEvent>,
+) {
+    for event in events.read() {
+        let GameplayEvent::Absorbed {
+            kind,
+            position,
+            scale,
+        } = *event
+        else {
+            continue;
+        };
+        let origin = position.truncate();
+        let radius = ORIGINAL_RADIUS * scale;
+        ParticlePreset::absorb(settings.palette.color(kind)).spawn(
+            &mut commands,
+            &mut random,
+            origin,
+            radius,
+        );
+        let effect = match kind {
+            ObstacleKind::Ice => Some(ParticlePreset::freeze()),
+            ObstacleKind::ScaleBust(true) => Some(ParticlePreset::bust_grow()),
+            ObstacleKind::ScaleBust(false) => Some(ParticlePreset::bust_shrink()),
+            ObstacleKind::Block | ObstacleKind::Poison => None,
+        };
+        if let Some(effect) = effect {
+            effect.spawn(&mut commands, &mut random, origin, radius);
+        }
+    }
+}
+
+pub fn death_particles_system(
+    mut commands: Commands,
+    mut random: ResMut<GlobalRng>,
+    mut events: EventReader<GameEvent>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let game_over = events
+        .read()
+        .filter(|event| matches!(event, GameEvent::GameOver))
+        .count();
+    if game_over == 0 {
+        return;
+    }
+    for transform in player_query.iter() {
+        ParticlePreset::death().spawn(
+            &mut commands,
+            &mut random,
+            transform.translation.truncate(),
+            ORIGINAL_RADIUS * transform.scale.x,
+        );
+    }
+}
+
+pub fn particle_update_system(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in query.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.);
+        sprite.color = particle.color();
+        sprite.custom_size = Some(Vec2::splat(particle.size()));
+    }
+}
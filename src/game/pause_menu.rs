@@ -0,0 +1,451 @@
+use bevy::{prelude::*, window::WindowFocused};
+use leafwing_input_manager::prelude::*;
+use std::time::Duration;
+
+use super::{
+    accessibility::ColorPalette,
+    audio::next_volume,
+    localization::{LocalizedText, Localizer},
+    scale_control::ScaleControl,
+    settings::Settings,
+    steering::ControlScheme,
+    *,
+};
+
+const COUNTDOWN: Duration = Duration::from_secs(3);
+const NAVIGATION_THRESHOLD: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+    Palette,
+    Glyphs,
+    Language,
+    Controls,
+    ScalePlayer,
+    ScaleControl,
+    Movement,
+    Absorption,
+    MasterVolume,
+    MusicVolume,
+    EffectsVolume,
+    Back,
+}
+
+impl MenuItem {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Resume => "pause.resume",
+            Self::Restart => "pause.restart",
+            Self::Settings => "pause.settings",
+            Self::Quit => "pause.quit",
+            Self::Palette => "settings.palette",
+            Self::Glyphs => "settings.glyphs",
+            Self::Language => "settings.language",
+            Self::Controls => "settings.controls",
+            Self::ScalePlayer => "settings.scale-player",
+            Self::ScaleControl => "settings.scale",
+            Self::Movement => "settings.movement",
+            Self::Absorption => "settings.absorption",
+            Self::MasterVolume => "settings.master-volume",
+            Self::MusicVolume => "settings.music-volume",
+            Self::EffectsVolume => "settings.effects-volume",
+            Self::Back => "settings.back",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuPage {
+    Main,
+    Settings,
+}
+
+impl MenuPage {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Main => "pause.title",
+            Self::Settings => "settings.title",
+        }
+    }
+
+    pub fn items(&self) -> &'static [MenuItem] {
+        match self {
+            Self::Main => &[
+                MenuItem::Resume,
+                MenuItem::Restart,
+                MenuItem::Settings,
+                MenuItem::Quit,
+            ],
+            Self::Settings => &[
+                MenuItem::Palette,
+                MenuItem::Glyphs,
+                MenuItem::Language,
+                MenuItem::Controls,
+                MenuItem::ScalePlayer,
+                MenuItem::ScaleControl,
+                MenuItem::Movement,
+                MenuItem::Absorption,
+                MenuItem::MasterVolume,
+                MenuItem::MusicVolume,
+                MenuItem::EffectsVolume,
+                MenuItem::Back,
+            ],
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PauseMenu {
+    page: MenuPage,
+    selected: usize,
+    countdown: Option<Timer>,
+    last_axis: f32,
+    scale_player: PlayerSlot,
+}
+
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self {
+            page: MenuPage::Main,
+            selected: 0,
+            countdown: None,
+            last_axis: 0.,
+            scale_player: PlayerSlot(0),
+        }
+    }
+}
+
+impl PauseMenu {
+    fn open(&mut self, page: MenuPage) {
+        self.page = page;
+        self.selected = 0;
+    }
+
+    fn navigate(&mut self, axis: f32) {
+        let pressed = axis.abs() >= NAVIGATION_THRESHOLD;
+        let was_pressed = self.last_axis.abs() >= NAVIGATION_THRESHOLD;
+        self.last_axis = axis;
+        if !pressed || was_pressed {
+            return;
+        }
+        let count = self.page.items().len();
+        self.selected = if axis > 0. {
+            (self.selected + count - 1) % count
+        } else {
+            (self.selected + 1) % count
+        };
+    }
+
+    fn selected_item(&self) -> MenuItem {
+        self.page.items()[self.selected]
+    }
+
+    fn next_scale_player(&mut self, slots: impl Iterator<Item = PlayerSlot>) {
+        let mut slots: Vec<PlayerSlot> = slots.collect();
+        slots.sort();
+        self.scale_player = slots
+            .iter()
+            .find(|slot| **slot > self.scale_player)
+            .or(slots.first())
+            .copied()
+            .unwrap_or(PlayerSlot(0));
+    }
+}
+
+#[derive(Component)]
+pub struct PauseMenuUi {
+    lines: Vec<(String, bool)>,
+}
+
+#[derive(Component)]
+pub struct MenuUi {}
+
+pub struct PauseMenuPlugin {}
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseMenu>()
+            .init_resource::<ActionState<PlayerAction>>()
+            .insert_resource(create_menu_input_map())
+            .add_systems(OnEnter(GameState::Pause), reset_pause_menu_system)
+            .add_systems(OnExit(GameState::Pause), despawn_pause_menu_system)
+            .add_systems(OnEnter(GameState::Menu), spawn_menu_system)
+            .add_systems(OnExit(GameState::Menu), despawn_menu_system)
+            .add_systems(
+                Update,
+                (
+                    focus_pause_system.run_if(in_state(GameState::Running)),
+                    (pause_menu_system, pause_menu_ui_system)
+                        .chain()
+                        .run_if(in_state(GameState::Pause)),
+                    menu_start_system.run_if(in_state(GameState::Menu)),
+                ),
+            );
+    }
+}
+
+impl Default for PauseMenuPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn create_menu_input_map() -> InputMap<PlayerAction> {
+    let mut input_map = create_input_map(ControlScheme::Standard);
+    input_map.insert(MouseButton::Left, PlayerAction::SwapScale);
+    insert_gamepad(&mut input_map);
+    input_map
+}
+
+pub fn focus_pause_system(
+    mut time: ResMut<Time<Virtual>>,
+    mut state: ResMut<NextState<GameState>>,
+    mut events: EventReader<WindowFocused>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    let lost_focus = events.read().filter(|event| !event.focused).count() > 0;
+    if lost_focus && !time.is_paused() {
+        state.set(GameState::Pause);
+        time.pause();
+        gameplay_events.send(GameplayEvent::Paused);
+    }
+}
+
+pub fn reset_pause_menu_system(mut menu: ResMut<PauseMenu>) {
+    *menu = PauseMenu::default();
+}
+
+pub fn despawn_pause_menu_system(mut commands: Commands, query: Query<Entity, With<PauseMenuUi>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn next_language(language: &Option<String>) -> Option<String> {
+    match language.as_deref() {
+        None => Some("en".to_string()),
+        Some("en") => Some("pt".to_string()),
+        _ => None,
+    }
+}
+
+pub fn pause_menu_system(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut menu: ResMut<PauseMenu>,
+    mut settings: ResMut<Settings>,
+    mut state: ResMut<NextState<GameState>>,
+    action_state: Res<ActionState<PlayerAction>>,
+    mut events: EventWriter<GameEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    player_query: Query<&PlayerSlot, With<Player>>,
+    clean_query: Query<Entity, (Without<Camera>, Without<Window>)>,
+) {
+    if let Some(countdown) = menu.countdown.as_mut() {
+        countdown.tick(real_time.delta());
+        if countdown.finished() {
+            menu.countdown = None;
+            state.set(GameState::Running);
+            time.unpause();
+            gameplay_events.send(GameplayEvent::Resumed);
+        }
+        return;
+    }
+    let axis = action_state
+        .axis_pair(PlayerAction::Move)
+        .map(|axis| axis.y())
+        .unwrap_or(0.);
+    menu.navigate(axis);
+    if action_state.just_pressed(PlayerAction::Pause) {
+        match menu.page {
+            MenuPage::Main => menu.countdown = Some(Timer::new(COUNTDOWN, TimerMode::Once)),
+            MenuPage::Settings => menu.open(MenuPage::Main),
+        }
+        return;
+    }
+    if !action_state.just_pressed(PlayerAction::SwapScale) {
+        return;
+    }
+    match menu.selected_item() {
+        MenuItem::Resume => menu.countdown = Some(Timer::new(COUNTDOWN, TimerMode::Once)),
+        MenuItem::Restart => {
+            events.send(GameEvent::Start);
+            for entity in clean_query.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        MenuItem::Settings => menu.open(MenuPage::Settings),
+        MenuItem::Quit => {
+            state.set(GameState::Menu);
+            for entity in clean_query.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        MenuItem::Palette => {
+            let index = ColorPalette::ALL
+                .iter()
+                .position(|palette| *palette == settings.palette)
+                .unwrap_or(0);
+            settings.palette = ColorPalette::ALL[(index + 1) % ColorPalette::ALL.len()];
+        }
+        MenuItem::Glyphs => settings.glyphs = !settings.glyphs,
+        MenuItem::Language => settings.language = next_language(&settings.language),
+        MenuItem::Controls => {
+            settings.control_scheme = match settings.control_scheme {
+                ControlScheme::Standard => ControlScheme::Mouse,
+                ControlScheme::Mouse => ControlScheme::Standard,
+            }
+        }
+        MenuItem::ScalePlayer => menu.next_scale_player(player_query.iter().copied()),
+        MenuItem::ScaleControl => {
+            let slot = menu.scale_player;
+            let control = settings.scale_control(slot).next();
+            settings.scale_controls.insert(slot.0, control);
+        }
+        MenuItem::Movement => settings.movement = settings.movement.next(),
+        MenuItem::Absorption => settings.absorption = settings.absorption.next(),
+        MenuItem::MasterVolume => settings.audio.master = next_volume(settings.audio.master),
+        MenuItem::MusicVolume => settings.audio.music = next_volume(settings.audio.music),
+        MenuItem::EffectsVolume => settings.audio.effects = next_volume(settings.audio.effects),
+        MenuItem::Back => menu.open(MenuPage::Main),
+    }
+}
+
+fn item_value(
+    item: MenuItem,
+    menu: &PauseMenu,
+    settings: &Settings,
+    localizer: &Localizer,
+) -> Option<String> {
+    let on_off = |value: bool| localizer.get(if value { "settings.on" } else { "settings.off" });
+    match item {
+        MenuItem::Palette => Some(settings.palette.name().to_string()),
+        MenuItem::Glyphs => Some(on_off(settings.glyphs)),
+        MenuItem::Language => Some(
+            settings
+                .language
+                .clone()
+                .unwrap_or_else(|| localizer.get("settings.system")),
+        ),
+        MenuItem::Controls => Some(settings.control_scheme.name().to_string()),
+        MenuItem::ScalePlayer => Some((menu.scale_player.0 + 1).to_string()),
+        MenuItem::ScaleControl => {
+            Some(settings.scale_control(menu.scale_player).name().to_string())
+        }
+        MenuItem::Movement => Some(settings.movement.name().to_string()),
+        MenuItem::Absorption => Some(settings.absorption.name().to_string()),
+        MenuItem::MasterVolume => Some(percent(settings.audio.master)),
+        MenuItem::MusicVolume => Some(percent(settings.audio.music)),
+        MenuItem::EffectsVolume => Some(percent(settings.audio.effects)),
+        _ => None,
+    }
+}
+
+fn percent(volume: f32) -> String {
+    format!("{}%", (volume * 100.).round())
+}
+
+fn menu_lines(menu: &PauseMenu, settings: &Settings, localizer: &Localizer) -> Vec<(String, bool)> {
+    if let Some(countdown) = menu.countdown.as_ref() {
+        let remaining = countdown.remaining_secs().ceil().max(1.);
+        return vec![(format!("{}", remaining), false)];
+    }
+    let mut lines = vec![(localizer.get(menu.page.title()), false)];
+    for (index, item) in menu.page.items().iter().enumerate() {
+        let label = localizer.get(item.key());
+        let line = match item_value(*item, menu, settings, localizer) {
+            Some(value) => format!("{}: {}", label, value),
+            None => label,
+        };
+        lines.push((line, index == menu.selected));
+    }
+    lines
+}
+
+pub fn pause_menu_ui_system(
+    mut commands: Commands,
+    menu: Res<PauseMenu>,
+    settings: Res<Settings>,
+    localizer: Localizer,
+    mut query: Query<(&mut PauseMenuUi, &mut Text)>,
+) {
+    let lines = menu_lines(&menu, &settings, &localizer);
+    let sections: Vec<TextSection> = lines
+        .iter()
+        .map(|(line, selected)| {
+            TextSection::new(
+                format!("{}{}\n", if *selected { "> " } else { "  " }, line),
+                TextStyle {
+                    font_size: 48.,
+                    color: if *selected {
+                        Color::BLACK
+                    } else {
+                        Color::DARK_GRAY
+                    },
+                    ..default()
+                },
+            )
+        })
+        .collect();
+    if let Ok((mut ui, mut text)) = query.get_single_mut() {
+        if ui.lines != lines {
+            *text = Text::from_sections(sections);
+            ui.lines = lines;
+        }
+        return;
+    }
+    commands.spawn((
+        TextBundle::from_sections(sections).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.),
+            left: Val::Percent(10.),
+            ..default()
+        }),
+        PauseMenuUi { lines },
+    ));
+}
+
+pub fn spawn_menu_system(mut commands: Commands) {
+    for (key, top, font_size) in [("menu.title", 30., 80.), ("menu.start", 50., 40.)] {
+        commands.spawn((
+            MenuUi {},
+            LocalizedText { key },
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size,
+                    color: Color::DARK_GRAY,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(top),
+                left: Val::Percent(10.),
+                ..default()
+            }),
+        ));
+    }
+}
+
+pub fn despawn_menu_system(mut commands: Commands, query: Query<Entity, With<MenuUi>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn menu_start_system(
+    action_state: Res<ActionState<PlayerAction>>,
+    mut events: EventWriter<GameEvent>,
+) {
+    if action_state.just_pressed(PlayerAction::Start)
+        || action_state.just_pressed(PlayerAction::SwapScale)
+    {
+        events.send(GameEvent::Start);
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Duration};
+
+use super::*;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct SaveData {
+    pub achievements: achievements::AchievementSave,
+    pub daily: daily::DailySave,
+    pub ghosts: ghost::GhostSave,
+    pub settings: settings::Settings,
+}
+
+#[derive(Resource)]
+pub struct SaveFile {
+    path: PathBuf,
+    data: SaveData,
+    dirty: bool,
+    timer: Timer,
+}
+
+impl SaveFile {
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let data = Self::read(&path).unwrap_or_default();
+        Self {
+            path,
+            data,
+            dirty: false,
+            timer: Timer::new(FLUSH_INTERVAL, TimerMode::Repeating),
+        }
+    }
+
+    pub fn data(&self) -> &SaveData {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut SaveData {
+        self.dirty = true;
+        &mut self.data
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read(path: &PathBuf) -> Option<SaveData> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match ron::from_str(&content) {
+            Ok(data) => Some(data),
+            Err(error) => {
+                warn!("ignoring unreadable save file {:?}: {}", path, error);
+                None
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn read(_path: &PathBuf) -> Option<SaveData> {
+        None
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        let content = match ron::ser::to_string_pretty(&self.data, default()) {
+            Ok(content) => content,
+            Err(error) => {
+                error!("could not serialize save file: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = std::fs::write(&self.path, content) {
+            error!("could not write save file {:?}: {}", self.path, error);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn flush(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Default for SaveFile {
+    fn default() -> Self {
+        Self::open("save.ron")
+    }
+}
+
+pub struct SavePlugin {}
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveFile>()
+            .add_systems(Last, flush_save_system);
+    }
+}
+
+impl Default for SavePlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn flush_save_system(
+    time: Res<Time<Real>>,
+    mut save: ResMut<SaveFile>,
+    mut events: EventReader<GameEvent>,
+    exit: EventReader<bevy::app::AppExit>,
+) {
+    let save = save.bypass_change_detection();
+    save.timer.tick(time.delta());
+    let game_event = events.read().count() > 0;
+    if save.timer.just_finished() || game_event || !exit.is_empty() {
+        save.flush();
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{console::ConsoleAppExt, settings::Settings, *};
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaleControl {
+    #[default]
+    Toggle,
+    Hold,
+    Analog,
+}
+
+impl ScaleControl {
+    pub const ALL: [Self; 3] = [Self::Toggle, Self::Hold, Self::Analog];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Hold => "hold",
+            Self::Analog => "analog",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|control| control.name() == name)
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|it| it == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+pub struct ScaleControlPlugin {}
+
+impl Plugin for ScaleControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command(
+            "scalecontrol",
+            "scalecontrol <toggle|hold|analog> [player]",
+            scale_control_command,
+        );
+    }
+}
+
+impl Default for ScaleControlPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn scale_control_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing control")?;
+    let control =
+        ScaleControl::from_name(name).ok_or_else(|| format!("unknown control '{}'", name))?;
+    let player = match args.get(1) {
+        Some(player) => player
+            .parse::<usize>()
+            .ok()
+            .filter(|player| *player > 0)
+            .ok_or_else(|| format!("invalid player '{}'", player))?,
+        None => 1,
+    };
+    world
+        .resource_mut::<Settings>()
+        .scale_controls
+        .insert(player - 1, control);
+    Ok(format!(
+        "scale control for player {} set to {}",
+        player,
+        control.name()
+    ))
+}
+
+pub fn player_scale_control_system(
+    settings: Res<Settings>,
+    mut query: Query<(&PlayerSlot, &mut Scale, &ActionState<PlayerAction>), With<Player>>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for (slot, mut scale, action_state) in query.iter_mut() {
+        let growing = scale.speed > 0.;
+        match settings.scale_control(*slot) {
+            ScaleControl::Toggle => {
+                if action_state.just_pressed(PlayerAction::SwapScale) {
+                    scale.swap();
+                }
+            }
+            ScaleControl::Hold => scale.hold(action_state.pressed(PlayerAction::SwapScale)),
+            ScaleControl::Analog => {
+                let trigger = if action_state.pressed(PlayerAction::SwapScale) {
+                    1.
+                } else {
+                    action_state.value(PlayerAction::Grow)
+                };
+                scale.throttle(trigger);
+            }
+        }
+        if growing != (scale.speed > 0.) {
+            gameplay_events.send(GameplayEvent::ScaleSwapped { speed: scale.speed });
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{
+    absorption::AbsorptionMode, accessibility::ColorPalette, audio::AudioSettings,
+    gamepad::GamepadIdentity, movement::MovementMode, save::SaveFile, scale_control::ScaleControl,
+    steering::ControlScheme, *,
+};
+
+#[derive(Resource, Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub palette: ColorPalette,
+    pub glyphs: bool,
+    pub language: Option<String>,
+    pub control_scheme: ControlScheme,
+    pub gamepads: BTreeMap<usize, GamepadIdentity>,
+    pub scale_controls: BTreeMap<usize, ScaleControl>,
+    pub movement: MovementMode,
+    pub absorption: AbsorptionMode,
+    pub audio: AudioSettings,
+}
+
+impl Settings {
+    pub fn gamepad(&self, slot: PlayerSlot, gamepads: &Gamepads) -> Option<Gamepad> {
+        self.gamepads
+            .get(&slot.0)
+            .and_then(|identity| identity.resolve(gamepads))
+    }
+
+    pub fn scale_control(&self, slot: PlayerSlot) -> ScaleControl {
+        self.scale_controls
+            .get(&slot.0)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+pub struct SettingsPlugin {}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_systems(PreStartup, load_settings_system)
+            .add_systems(
+                Last,
+                store_settings_system
+                    .before(save::flush_save_system)
+                    .run_if(resource_changed::<Settings>()),
+            );
+    }
+}
+
+impl Default for SettingsPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn load_settings_system(mut settings: ResMut<Settings>, save: Res<SaveFile>) {
+    *settings = save.data().settings.clone();
+}
+
+pub fn store_settings_system(settings: Res<Settings>, mut save: ResMut<SaveFile>) {
+    if save.data().settings != *settings {
+        save.data_mut().settings = settings.clone();
+    }
+}
@@ -0,0 +1,437 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Duration};
+
+use super::{
+    console::ConsoleAppExt,
+    game_mode::{ActiveGameMode, GameModeConfig, GameModeState},
+    gate::{PassedGates, ScaleGate},
+    terrain::{TerrainChunk, TerrainGenerator},
+    tutorial::{Tutorial, TutorialStep},
+    *,
+};
+
+const SAVE_KEY: KeyCode = KeyCode::F5;
+const LOAD_KEY: KeyCode = KeyCode::F9;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlayerSnapshot {
+    pub translation: Vec3,
+    pub scale: Vec3,
+    pub scale_speed: f32,
+    pub velocity: Vec2,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum EffectSnapshot {
+    Bust {
+        speed: f32,
+        duration: f32,
+        elapsed: f32,
+    },
+    Frozen {
+        duration: f32,
+        elapsed: f32,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LifetimeSnapshot {
+    pub duration: f32,
+    pub elapsed: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ObstacleSnapshot {
+    pub kind: ObstacleKind,
+    pub translation: Vec3,
+    pub scale: f32,
+    pub velocity: Vec2,
+    pub enemy: Option<Enemy>,
+    pub lifetime: Option<LifetimeSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunSnapshot {
+    pub seed: u64,
+    pub score: Duration,
+    pub camera_translation: Vec3,
+    pub camera_velocity: Vec2,
+    pub factory_interval: Duration,
+    pub factory_elapsed: Duration,
+    pub factory_rng: RngComponent,
+    pub terrain_chunk: Option<i64>,
+    pub passed_gates: Vec<i64>,
+    pub play_field: PlayField,
+    pub god_mode: bool,
+    pub tutorial: Option<TutorialStep>,
+    pub mode: GameModeState,
+    pub player: PlayerSnapshot,
+    pub effects: Vec<EffectSnapshot>,
+    pub obstacles: Vec<ObstacleSnapshot>,
+}
+
+#[derive(Resource)]
+pub struct RunSnapshotFile {
+    path: PathBuf,
+}
+
+impl RunSnapshotFile {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read(&self) -> Option<RunSnapshot> {
+        let content = std::fs::read_to_string(&self.path).ok()?;
+        match ron::from_str(&content) {
+            Ok(snapshot) => Some(snapshot),
+            Err(error) => {
+                warn!("ignoring unreadable run save {:?}: {}", self.path, error);
+                None
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn read(&self) -> Option<RunSnapshot> {
+        None
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write(&self, snapshot: &RunSnapshot) {
+        let content = match ron::ser::to_string_pretty(snapshot, default()) {
+            Ok(content) => content,
+            Err(error) => {
+                error!("could not serialize run save: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = std::fs::write(&self.path, content) {
+            error!("could not write run save {:?}: {}", self.path, error);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn write(&self, _snapshot: &RunSnapshot) {}
+}
+
+impl Default for RunSnapshotFile {
+    fn default() -> Self {
+        Self {
+            path: "run.ron".into(),
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunSnapshotEvent {
+    Save,
+    Load,
+}
+
+#[derive(Resource)]
+pub struct PendingSnapshot(pub RunSnapshot);
+
+pub struct SnapshotPlugin {}
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RunSnapshotEvent>()
+            .init_resource::<RunSnapshotFile>()
+            .add_console_command("save", "save", save_command)
+            .add_console_command("load", "load", load_command)
+            .add_systems(
+                Update,
+                (
+                    snapshot_key_system,
+                    save_snapshot_system
+                        .run_if(in_state(GameState::Running).or_else(in_state(GameState::Pause))),
+                    load_snapshot_system.run_if(
+                        in_state(GameState::Running)
+                            .or_else(in_state(GameState::Pause))
+                            .or_else(in_state(GameState::Over))
+                            .or_else(in_state(GameState::Menu)),
+                    ),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                apply_snapshot_system
+                    .run_if(in_state(GameState::Running))
+                    .run_if(resource_exists::<PendingSnapshot>())
+                    .before(spawn_obstacle_system),
+            );
+    }
+}
+
+impl Default for SnapshotPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn save_command(_args: &[&str], world: &mut World) -> Result<String, String> {
+    world.send_event(RunSnapshotEvent::Save);
+    Ok(String::new())
+}
+
+fn load_command(_args: &[&str], world: &mut World) -> Result<String, String> {
+    world.send_event(RunSnapshotEvent::Load);
+    Ok(String::new())
+}
+
+pub fn snapshot_key_system(keys: Res<Input<KeyCode>>, mut events: EventWriter<RunSnapshotEvent>) {
+    if keys.just_pressed(SAVE_KEY) {
+        events.send(RunSnapshotEvent::Save);
+    }
+    if keys.just_pressed(LOAD_KEY) {
+        events.send(RunSnapshotEvent::Load);
+    }
+}
+
+pub fn save_snapshot_system(
+    file: Res<RunSnapshotFile>,
+    seed: Res<RunSeed>,
+    play_field: Res<PlayField>,
+    god_mode: Res<GodMode>,
+    tutorial: Option<Res<Tutorial>>,
+    mode: Res<ActiveGameMode>,
+    mut events: EventReader<RunSnapshotEvent>,
+    score_query: Query<&TimeScore>,
+    camera_query: Query<(&Transform, &Velocity), With<Camera>>,
+    factory_query: Query<(&ObstacleFactoryComponent, &RngComponent)>,
+    chunk_query: Query<&TerrainChunk>,
+    gate_query: Query<(&ScaleGate, &TerrainChunk)>,
+    player_query: Query<(Entity, &Transform, &Scale, Option<&Velocity>), With<Player>>,
+    bust_query: Query<(&BustEffect, &Temporary)>,
+    frozen_query: Query<(&FrozenEffect, &Temporary)>,
+    obstacle_query: Query<(
+        &Obstacle,
+        &Transform,
+        Option<&Velocity>,
+        Option<&Enemy>,
+        Option<&Temporary>,
+    )>,
+) {
+    let save = events
+        .read()
+        .filter(|event| **event == RunSnapshotEvent::Save)
+        .count();
+    if save == 0 {
+        return;
+    }
+    let Ok((camera_transform, camera_velocity)) = camera_query.get_single() else {
+        return;
+    };
+    let Ok((factory, random)) = factory_query.get_single() else {
+        return;
+    };
+    let Ok((player, player_transform, scale, player_velocity)) = player_query.get_single() else {
+        return;
+    };
+    let bust_effects = bust_query
+        .iter()
+        .filter(|(effect, _)| effect.target == player)
+        .map(|(effect, temporary)| EffectSnapshot::Bust {
+            speed: effect.speed,
+            duration: temporary.timer.duration().as_secs_f32(),
+            elapsed: temporary.timer.elapsed_secs(),
+        });
+    let frozen_effects = frozen_query
+        .iter()
+        .filter(|(effect, _)| effect.target == player)
+        .map(|(_, temporary)| EffectSnapshot::Frozen {
+            duration: temporary.timer.duration().as_secs_f32(),
+            elapsed: temporary.timer.elapsed_secs(),
+        });
+    let snapshot = RunSnapshot {
+        seed: seed.0,
+        score: score_query
+            .iter()
+            .map(TimeScore::elapsed)
+            .max()
+            .unwrap_or_default(),
+        camera_translation: camera_transform.translation,
+        camera_velocity: camera_velocity.linvel,
+        factory_interval: factory.timer.duration(),
+        factory_elapsed: factory.timer.elapsed(),
+        factory_rng: random.clone(),
+        terrain_chunk: chunk_query.iter().map(TerrainChunk::index).min(),
+        passed_gates: gate_query
+            .iter()
+            .filter(|(gate, _)| gate.passed())
+            .map(|(_, chunk)| chunk.index())
+            .collect(),
+        play_field: *play_field,
+        god_mode: god_mode.0,
+        tutorial: tutorial.map(|it| it.step()),
+        mode: mode.mode.state(),
+        player: PlayerSnapshot {
+            translation: player_transform.translation,
+            scale: player_transform.scale,
+            scale_speed: scale.speed,
+            velocity: player_velocity.map(|it| it.linvel).unwrap_or_default(),
+        },
+        effects: bust_effects.chain(frozen_effects).collect(),
+        obstacles: obstacle_query
+            .iter()
+            .map(
+                |(obstacle, transform, velocity, enemy, temporary)| ObstacleSnapshot {
+                    kind: obstacle.kind,
+                    translation: transform.translation,
+                    scale: transform.scale.x,
+                    velocity: velocity.map(|it| it.linvel).unwrap_or_default(),
+                    enemy: enemy.cloned(),
+                    lifetime: temporary.map(|it| LifetimeSnapshot {
+                        duration: it.timer.duration().as_secs_f32(),
+                        elapsed: it.timer.elapsed_secs(),
+                    }),
+                },
+            )
+            .collect(),
+    };
+    file.write(&snapshot);
+    info!("run saved to {:?}", file.path);
+}
+
+pub fn load_snapshot_system(
+    mut commands: Commands,
+    file: Res<RunSnapshotFile>,
+    mut mode_config: ResMut<GameModeConfig>,
+    mut events: EventReader<RunSnapshotEvent>,
+    mut game_events: EventWriter<GameEvent>,
+    clean_query: Query<Entity, (Without<Camera>, Without<Window>)>,
+) {
+    let load = events
+        .read()
+        .filter(|event| **event == RunSnapshotEvent::Load)
+        .count();
+    if load == 0 {
+        return;
+    }
+    let Some(snapshot) = file.read() else {
+        warn!("no run save found at {:?}", file.path);
+        return;
+    };
+    commands.insert_resource(snapshot.play_field);
+    commands.insert_resource(GodMode(snapshot.god_mode));
+    mode_config.kind = snapshot.mode.kind();
+    match snapshot.tutorial {
+        Some(step) => commands.insert_resource(Tutorial::at(step)),
+        None => commands.remove_resource::<Tutorial>(),
+    }
+    commands.insert_resource(PendingSnapshot(snapshot));
+    game_events.send(GameEvent::Start);
+    for entity in clean_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn timer(duration: f32, elapsed: f32) -> Timer {
+    let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+    timer.set_elapsed(Duration::from_secs_f32(elapsed));
+    timer
+}
+
+pub fn apply_snapshot_system(
+    mut commands: Commands,
+    pending: Res<PendingSnapshot>,
+    atlases: atlas::Atlases,
+    terrain: Option<ResMut<TerrainGenerator>>,
+    mut passed_gates: ResMut<PassedGates>,
+    mut mode: ResMut<ActiveGameMode>,
+    mut score_query: Query<&mut TimeScore>,
+    mut camera_query: Query<(&mut Transform, &mut Velocity), With<Camera>>,
+    mut factory_query: Query<(&mut ObstacleFactoryComponent, &mut RngComponent)>,
+    chunk_query: Query<Entity, With<TerrainChunk>>,
+    mut player_query: Query<(Entity, &mut Transform, &mut Scale), (With<Player>, Without<Camera>)>,
+    obstacle_query: Query<Entity, With<Obstacle>>,
+) {
+    let snapshot = &pending.0;
+    let Ok((player, mut player_transform, mut scale)) = player_query.get_single_mut() else {
+        return;
+    };
+    commands.insert_resource(RunSeed(snapshot.seed));
+    for mut score in score_query.iter_mut() {
+        score.elapsed_time = snapshot.score;
+    }
+    for (mut transform, mut velocity) in camera_query.iter_mut() {
+        transform.translation = snapshot.camera_translation;
+        velocity.linvel = snapshot.camera_velocity;
+    }
+    for (mut factory, mut random) in factory_query.iter_mut() {
+        factory.timer.set_duration(snapshot.factory_interval);
+        factory.timer.set_elapsed(snapshot.factory_elapsed);
+        *random = snapshot.factory_rng.clone();
+    }
+    passed_gates.0 = snapshot.passed_gates.clone();
+    mode.mode = snapshot.mode.restore();
+    if let (Some(mut terrain), Some(chunk)) = (terrain, snapshot.terrain_chunk) {
+        terrain.restart_at(chunk);
+        for entity in chunk_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    player_transform.translation = snapshot.player.translation;
+    player_transform.scale = snapshot.player.scale;
+    scale.speed = snapshot.player.scale_speed;
+    commands
+        .entity(player)
+        .insert(Velocity::linear(snapshot.player.velocity));
+    for effect in snapshot.effects.iter() {
+        match *effect {
+            EffectSnapshot::Bust {
+                speed,
+                duration,
+                elapsed,
+            } => {
+                commands.spawn((
+                    BustEffect {
+                        target: player,
+                        speed,
+                    },
+                    Temporary {
+                        timer: timer(duration, elapsed),
+                    },
+                ));
+            }
+            EffectSnapshot::Frozen { duration, elapsed } => {
+                commands.spawn((
+                    FrozenEffect { target: player },
+                    Temporary {
+                        timer: timer(duration, elapsed),
+                    },
+                ));
+            }
+        }
+    }
+
+    for entity in obstacle_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for obstacle in snapshot.obstacles.iter() {
+        let event = SpawnObstacleEvent {
+            color: obstacle.kind.get_color(),
+            position: obstacle.translation,
+            radius: ORIGINAL_RADIUS,
+            scale: obstacle.scale,
+            kind: obstacle.kind,
+        };
+        let Some(entity) = spawn_obstacle(&mut commands, &atlases, &event) else {
+            continue;
+        };
+        commands
+            .entity(entity)
+            .insert(Velocity::linear(obstacle.velocity));
+        if let Some(enemy) = obstacle.enemy.clone() {
+            commands.entity(entity).insert(enemy);
+        }
+        if let Some(lifetime) = &obstacle.lifetime {
+            commands.entity(entity).insert(Temporary {
+                timer: timer(lifetime.duration, lifetime.elapsed),
+            });
+        }
+    }
+    commands.remove_resource::<PendingSnapshot>();
+}
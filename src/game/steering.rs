@@ -0,0 +1,105 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use leafwing_input_manager::{axislike::DualAxisData, plugin::InputManagerSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{console::ConsoleAppExt, settings::Settings, *};
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlScheme {
+    #[default]
+    Standard,
+    Mouse,
+}
+
+impl ControlScheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Mouse => "mouse",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Standard, Self::Mouse]
+            .into_iter()
+            .find(|scheme| scheme.name() == name)
+    }
+}
+
+pub struct SteeringPlugin {}
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_console_command("controls", "controls <standard|mouse>", controls_command)
+            .add_systems(
+                PreUpdate,
+                mouse_steering_system
+                    .in_set(InputManagerSystem::ManualControl)
+                    .run_if(|settings: Res<Settings>| {
+                        settings.control_scheme == ControlScheme::Mouse
+                    }),
+            )
+            .add_systems(
+                Update,
+                update_input_map_system
+                    .run_if(resource_changed::<Settings>().or_else(resource_changed::<Gamepads>())),
+            );
+    }
+}
+
+impl Default for SteeringPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+fn controls_command(args: &[&str], world: &mut World) -> Result<String, String> {
+    let name = args.first().ok_or("missing scheme")?;
+    let scheme =
+        ControlScheme::from_name(name).ok_or_else(|| format!("unknown scheme '{}'", name))?;
+    world.resource_mut::<Settings>().control_scheme = scheme;
+    Ok(format!("controls set to {}", scheme.name()))
+}
+
+pub fn update_input_map_system(
+    settings: Res<Settings>,
+    gamepads: Res<Gamepads>,
+    mut query: Query<(&PlayerSlot, &mut InputMap<PlayerAction>), With<Player>>,
+) {
+    for (slot, mut input_map) in query.iter_mut() {
+        let gamepad = gamepad::player_gamepad(&settings, &gamepads, *slot);
+        *input_map = create_input_manager(settings.control_scheme, gamepad).input_map;
+    }
+}
+
+pub fn mouse_steering_system(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut player_query: Query<(&Transform, &mut ActionState<PlayerAction>), With<Player>>,
+    toggle_actions: Res<ToggleActions<PlayerAction>>,
+) {
+    if !toggle_actions.enabled {
+        return;
+    }
+    let cursor = window_query
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor)
+        });
+    for (transform, mut action_state) in player_query.iter_mut() {
+        let diff = cursor
+            .map(|cursor| cursor - transform.translation.truncate())
+            .unwrap_or(Vec2::ZERO);
+        let direction = if diff.length() > ORIGINAL_RADIUS * transform.scale.x {
+            diff.normalize_or_zero()
+        } else {
+            Vec2::ZERO
+        };
+        let data = action_state.action_data_mut(PlayerAction::Move);
+        data.axis_pair = Some(DualAxisData::from_xy(direction));
+        data.value = direction.length();
+    }
+}
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use super::{game_mode::ActiveGameMode, *};
+
+#[derive(Resource, Default, Clone, Debug)]
+pub struct TelemetryConfig {
+    pub path: Option<PathBuf>,
+}
+
+impl TelemetryConfig {
+    pub fn enabled(config: Res<TelemetryConfig>) -> bool {
+        config.path.is_some()
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Won,
+    Lost,
+}
+
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    Spawn {
+        kind: ObstacleKind,
+        x: f32,
+        y: f32,
+        scale: f32,
+    },
+    Absorbed {
+        kind: ObstacleKind,
+        x: f32,
+        y: f32,
+        scale: f32,
+    },
+    EffectApplied {
+        kind: ObstacleKind,
+    },
+    ScaleSwapped {
+        speed: f32,
+    },
+    Paused,
+    Resumed,
+    Died {
+        cause: DeathCause,
+    },
+    GameOver {
+        outcome: RunOutcome,
+    },
+}
+
+impl TelemetryEvent {
+    pub fn from_gameplay(event: &GameplayEvent) -> Self {
+        match *event {
+            GameplayEvent::Absorbed {
+                kind,
+                position,
+                scale,
+            } => Self::Absorbed {
+                kind,
+                x: position.x,
+                y: position.y,
+                scale,
+            },
+            GameplayEvent::EffectApplied { kind } => Self::EffectApplied { kind },
+            GameplayEvent::ScaleSwapped { speed } => Self::ScaleSwapped { speed },
+            GameplayEvent::Paused => Self::Paused,
+            GameplayEvent::Resumed => Self::Resumed,
+            GameplayEvent::Died { cause } => Self::Died { cause },
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct TelemetryRecord {
+    pub seed: Option<u64>,
+    pub time: f32,
+    pub real_time: f32,
+    pub player_x: f32,
+    pub player_y: f32,
+    pub player_scale: f32,
+    #[serde(flatten)]
+    pub event: TelemetryEvent,
+}
+
+#[derive(Resource, Default)]
+pub struct TelemetryWriter {
+    file: Option<BufWriter<File>>,
+}
+
+impl TelemetryWriter {
+    pub fn open(path: &PathBuf) -> Self {
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => Self {
+                file: Some(BufWriter::new(file)),
+            },
+            Err(error) => {
+                error!("could not open telemetry log {:?}: {}", path, error);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn write(&mut self, record: &TelemetryRecord) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let result = serde_json::to_writer(&mut *file, record)
+            .map_err(std::io::Error::from)
+            .and_then(|_| file.write_all(b"\n"));
+        if let Err(error) = result {
+            error!("could not write telemetry record: {}", error);
+            self.file = None;
+        }
+    }
+
+    pub fn flush(&mut self) {
+        if let Some(file) = self.file.as_mut() {
+            if let Err(error) = file.flush() {
+                error!("could not flush telemetry log: {}", error);
+            }
+        }
+    }
+}
+
+pub struct TelemetryPlugin {}
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TelemetryConfig>()
+            .init_resource::<TelemetryWriter>()
+            .add_systems(
+                Startup,
+                open_telemetry_system.run_if(TelemetryConfig::enabled),
+            )
+            .add_systems(
+                PostUpdate,
+                telemetry_system.run_if(TelemetryConfig::enabled),
+            );
+    }
+}
+
+impl Default for TelemetryPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn open_telemetry_system(config: Res<TelemetryConfig>, mut writer: ResMut<TelemetryWriter>) {
+    if let Some(path) = config.path.as_ref() {
+        *writer = TelemetryWriter::open(path);
+    }
+}
+
+pub fn telemetry_system(
+    time: Res<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    seed: Option<Res<RunSeed>>,
+    mut writer: ResMut<TelemetryWriter>,
+    mut spawn_events: EventReader<SpawnObstacleEvent>,
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut game_events: EventReader<GameEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let player = player_query
+        .get_single()
+        .map(|transform| (transform.translation.truncate(), transform.scale.x))
+        .unwrap_or((Vec2::ZERO, 0.));
+    let record = |event: TelemetryEvent| TelemetryRecord {
+        seed: seed.as_ref().map(|seed| seed.0),
+        time: time.elapsed_seconds(),
+        real_time: real_time.elapsed_seconds(),
+        player_x: player.0.x,
+        player_y: player.0.y,
+        player_scale: player.1,
+        event,
+    };
+    let spawns = spawn_events.read().map(|event| TelemetryEvent::Spawn {
+        kind: event.kind,
+        x: event.position.x,
+        y: event.position.y,
+        scale: event.scale,
+    });
+    let gameplay = gameplay_events.read().map(TelemetryEvent::from_gameplay);
+    let outcome = match mode {
+        Some(mode) if mode.mode.is_won() => RunOutcome::Won,
+        _ => RunOutcome::Lost,
+    };
+    let game_over = game_events
+        .read()
+        .filter(|event| matches!(event, GameEvent::GameOver))
+        .map(|_| TelemetryEvent::GameOver { outcome });
+    let mut written = false;
+    for event in spawns.chain(gameplay).chain(game_over) {
+        writer.write(&record(event));
+        written = true;
+    }
+    if written {
+        writer.flush();
+    }
+}
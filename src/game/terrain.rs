@@ -0,0 +1,263 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_turborand::{prelude::*, DelegatedRng};
+
+use super::{gate::spawn_scale_gate, settings::Settings, *};
+
+pub const CHUNK_LENGTH: f32 = 512.;
+const FIELD_HALF_WIDTH: f32 = 720. / 2.;
+const VIEW_HALF_HEIGHT: f32 = 1080. / 2.;
+const WALL_THICKNESS: f32 = 32.;
+const MIN_GAP: f32 = 48.;
+const MAX_GAP: f32 = 256.;
+const GATE_POST_SIZE: f32 = 24.;
+
+#[derive(Component)]
+pub struct TerrainChunk {
+    index: i64,
+}
+
+impl TerrainChunk {
+    pub fn index(&self) -> i64 {
+        self.index
+    }
+}
+
+#[derive(Resource)]
+pub struct TerrainGenerator {
+    next_chunk: i64,
+}
+
+impl TerrainGenerator {
+    pub fn restart_at(&mut self, chunk: i64) {
+        self.next_chunk = chunk;
+    }
+}
+
+impl Default for TerrainGenerator {
+    fn default() -> Self {
+        Self { next_chunk: 1 }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ChunkPattern {
+    Open,
+    Corridor { gap: f32, center: f32 },
+    Narrowing { gap: f32, center: f32 },
+    Pillars { count: usize },
+    Chicane { flipped: bool },
+    Gate { center: f32, min: f32, max: f32 },
+}
+
+impl ChunkPattern {
+    pub fn random(random: &mut RngComponent) -> Self {
+        let gap = MIN_GAP + random.f32() * (MAX_GAP - MIN_GAP);
+        let center = random.f32_normalized() * (FIELD_HALF_WIDTH - gap / 2. - WALL_THICKNESS);
+        match random.u8(0..=5) {
+            0 => Self::Open,
+            1 => Self::Corridor {
+                gap: gap * 2.,
+                center,
+            },
+            2 => Self::Narrowing { gap, center },
+            3 => Self::Pillars {
+                count: random.usize(2..=4),
+            },
+            4 => Self::Chicane {
+                flipped: random.bool(),
+            },
+            _ => {
+                let min = 0.3 + random.f32() * 1.2;
+                let max = min + 0.5 + random.f32();
+                let gap = gate_gap(max);
+                let center = random.f32_normalized()
+                    * (FIELD_HALF_WIDTH - gap / 2. - GATE_POST_SIZE * 2. - WALL_THICKNESS);
+                Self::Gate { center, min, max }
+            }
+        }
+    }
+
+    // Pieces are (center, half_extents) in chunk space: x across the field, y along the scroll.
+    pub fn pieces(&self, random: &mut RngComponent) -> Vec<(Vec2, Vec2)> {
+        match *self {
+            Self::Open => vec![],
+            Self::Corridor { gap, center } => {
+                let gap = f32::min(gap, FIELD_HALF_WIDTH * 2. - WALL_THICKNESS * 2.);
+                let center = center.clamp(
+                    -FIELD_HALF_WIDTH + gap / 2. + WALL_THICKNESS,
+                    FIELD_HALF_WIDTH - gap / 2. - WALL_THICKNESS,
+                );
+                side_walls(gap, center, CHUNK_LENGTH / 2., CHUNK_LENGTH / 2.)
+            }
+            Self::Narrowing { gap, center } => {
+                side_walls(gap, center, CHUNK_LENGTH / 2., WALL_THICKNESS)
+            }
+            Self::Pillars { count } => (0..count)
+                .map(|_| {
+                    let half_size = 24. + random.f32() * 24.;
+                    let position = Vec2::new(
+                        random.f32_normalized() * (FIELD_HALF_WIDTH - half_size),
+                        half_size + random.f32() * (CHUNK_LENGTH - half_size * 2.),
+                    );
+                    (position, Vec2::splat(half_size))
+                })
+                .collect(),
+            Self::Chicane { flipped } => {
+                let side = if flipped { -1. } else { 1. };
+                let half_width = FIELD_HALF_WIDTH * 0.65 / 2.;
+                vec![
+                    (
+                        Vec2::new(side * (-FIELD_HALF_WIDTH + half_width), CHUNK_LENGTH / 3.),
+                        Vec2::new(half_width, WALL_THICKNESS / 2.),
+                    ),
+                    (
+                        Vec2::new(
+                            side * (FIELD_HALF_WIDTH - half_width),
+                            CHUNK_LENGTH * 2. / 3.,
+                        ),
+                        Vec2::new(half_width, WALL_THICKNESS / 2.),
+                    ),
+                ]
+            }
+            Self::Gate { center, max, .. } => {
+                let gap = gate_gap(max);
+                let mut pieces = side_walls(
+                    gap + GATE_POST_SIZE * 4.,
+                    center,
+                    CHUNK_LENGTH / 2.,
+                    WALL_THICKNESS / 2.,
+                );
+                for side in [-1., 1.] {
+                    pieces.push((
+                        Vec2::new(
+                            center + side * (gap / 2. + GATE_POST_SIZE),
+                            CHUNK_LENGTH / 2.,
+                        ),
+                        Vec2::splat(GATE_POST_SIZE),
+                    ));
+                }
+                pieces
+            }
+        }
+    }
+}
+
+fn gate_gap(max: f32) -> f32 {
+    ORIGINAL_RADIUS * 2. * max + 8.
+}
+
+fn side_walls(gap: f32, center: f32, y: f32, half_length: f32) -> Vec<(Vec2, Vec2)> {
+    let left_width = (center - gap / 2.) + FIELD_HALF_WIDTH;
+    let right_width = FIELD_HALF_WIDTH - (center + gap / 2.);
+    vec![
+        (
+            Vec2::new(-FIELD_HALF_WIDTH + left_width / 2., y),
+            Vec2::new(left_width / 2., half_length),
+        ),
+        (
+            Vec2::new(FIELD_HALF_WIDTH - right_width / 2., y),
+            Vec2::new(right_width / 2., half_length),
+        ),
+    ]
+}
+
+pub fn chunk_rng(seed: u64, index: i64) -> RngComponent {
+    RngComponent::with_seed(seed ^ (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+pub struct TerrainPlugin {}
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Startup), reset_terrain_system)
+            .add_systems(
+                Update,
+                (generate_terrain_system, despawn_terrain_system)
+                    .run_if(in_state(GameState::Running))
+                    .run_if(resource_equals(PlayField::Scrolling)),
+            );
+    }
+}
+
+impl Default for TerrainPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn reset_terrain_system(mut commands: Commands) {
+    commands.insert_resource(TerrainGenerator::default());
+}
+
+pub fn generate_terrain_system(
+    mut commands: Commands,
+    seed: Res<RunSeed>,
+    settings: Res<Settings>,
+    mut generator: ResMut<TerrainGenerator>,
+    camera_query: Query<(&Transform, &Velocity), With<Camera>>,
+) {
+    let Ok((camera_transform, camera_velocity)) = camera_query.get_single() else {
+        return;
+    };
+    let forward = camera_velocity.linvel.normalize_or_zero();
+    if forward == Vec2::ZERO {
+        return;
+    }
+    let across = forward.rotate(Vec2::from_angle(-PI / 2.));
+    let rotation = Quat::from_rotation_z(Vec2::X.angle_between(across));
+    let camera_distance = camera_transform.translation.truncate().dot(forward);
+    let horizon = camera_distance + VIEW_HALF_HEIGHT + CHUNK_LENGTH;
+    while generator.next_chunk as f32 * CHUNK_LENGTH < horizon {
+        let index = generator.next_chunk;
+        let mut random = chunk_rng(seed.0, index);
+        let pattern = ChunkPattern::random(&mut random);
+        let chunk_start = index as f32 * CHUNK_LENGTH;
+        if let ChunkPattern::Gate { center, min, max } = pattern {
+            let position = across * center + forward * (chunk_start + CHUNK_LENGTH / 2.);
+            let transform =
+                Transform::from_translation(position.extend(0.5)).with_rotation(rotation);
+            let gate = spawn_scale_gate(&mut commands, transform, gate_gap(max) / 2., min, max);
+            commands.entity(gate).insert(TerrainChunk { index });
+        }
+        for (center, half_extents) in pattern.pieces(&mut random) {
+            let position = across * center.x + forward * (chunk_start + center.y);
+            commands
+                .spawn(TerrainChunk { index })
+                .insert(Wall {})
+                .insert(Collider::cuboid(half_extents.x, half_extents.y))
+                .insert(RigidBody::Fixed)
+                .insert(CollisionGroups::new(Group::all(), Group::all()))
+                .insert(SolverGroups::new(Group::all(), Group::all()))
+                .insert(SpriteBundle {
+                    sprite: Sprite {
+                        color: settings.palette.color(ObstacleKind::Block),
+                        custom_size: Some(half_extents * 2.),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(position.extend(0.5))
+                        .with_rotation(rotation),
+                    ..default()
+                });
+        }
+        generator.next_chunk += 1;
+    }
+}
+
+pub fn despawn_terrain_system(
+    mut commands: Commands,
+    camera_query: Query<(&Transform, &Velocity), With<Camera>>,
+    query: Query<(Entity, &TerrainChunk)>,
+) {
+    let Ok((camera_transform, camera_velocity)) = camera_query.get_single() else {
+        return;
+    };
+    let forward = camera_velocity.linvel.normalize_or_zero();
+    let camera_distance = camera_transform.translation.truncate().dot(forward);
+    let behind = camera_distance - VIEW_HALF_HEIGHT - CHUNK_LENGTH;
+    for (entity, chunk) in query.iter() {
+        if ((chunk.index + 1) as f32 * CHUNK_LENGTH) < behind {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use bevy::{
+    input::touch::{TouchInput, TouchPhase},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use leafwing_input_manager::{axislike::DualAxisData, plugin::InputManagerSystem, prelude::*};
+
+use super::*;
+
+const JOYSTICK_RADIUS: f32 = 80.;
+const PAUSE_BUTTON_SIZE: f32 = 96.;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JoystickTouch {
+    pub id: u64,
+    pub origin: Vec2,
+    pub position: Vec2,
+}
+
+impl JoystickTouch {
+    pub fn axis(&self) -> Vec2 {
+        let offset = (self.position - self.origin) / JOYSTICK_RADIUS;
+        Vec2::new(offset.x, -offset.y).clamp_length_max(1.)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchZone {
+    Joystick,
+    SwapScale,
+    Pause,
+}
+
+impl TouchZone {
+    pub fn at(position: Vec2, window_size: Vec2) -> Self {
+        if position.x <= PAUSE_BUTTON_SIZE && position.y <= PAUSE_BUTTON_SIZE {
+            Self::Pause
+        } else if position.x < window_size.x / 2. {
+            Self::Joystick
+        } else {
+            Self::SwapScale
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct TouchControls {
+    pub active: bool,
+    pub joystick: Option<JoystickTouch>,
+    joystick_released: bool,
+    swap_pressed: bool,
+    pause_pressed: bool,
+}
+
+impl TouchControls {
+    pub fn handle(&mut self, event: &TouchInput, window_size: Vec2) {
+        self.active = true;
+        match event.phase {
+            TouchPhase::Started => match TouchZone::at(event.position, window_size) {
+                TouchZone::Pause => self.pause_pressed = true,
+                TouchZone::SwapScale => self.swap_pressed = true,
+                TouchZone::Joystick if self.joystick.is_none() => {
+                    self.joystick = Some(JoystickTouch {
+                        id: event.id,
+                        origin: event.position,
+                        position: event.position,
+                    });
+                }
+                TouchZone::Joystick => (),
+            },
+            TouchPhase::Moved => {
+                if let Some(joystick) = self.joystick.as_mut().filter(|it| it.id == event.id) {
+                    joystick.position = event.position;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Canceled => {
+                if self.joystick.map(|it| it.id) == Some(event.id) {
+                    self.joystick = None;
+                    self.joystick_released = true;
+                }
+            }
+        }
+    }
+
+    pub fn apply(&mut self, action_state: &mut ActionState<PlayerAction>) {
+        if let Some(joystick) = self.joystick {
+            let axis = joystick.axis();
+            let data = action_state.action_data_mut(PlayerAction::Move);
+            data.axis_pair = Some(DualAxisData::from_xy(axis));
+            data.value = axis.length();
+        } else if self.joystick_released {
+            let data = action_state.action_data_mut(PlayerAction::Move);
+            data.axis_pair = Some(DualAxisData::from_xy(Vec2::ZERO));
+            data.value = 0.;
+        }
+        if self.swap_pressed {
+            action_state.press(PlayerAction::SwapScale);
+        }
+        if self.pause_pressed {
+            action_state.press(PlayerAction::Pause);
+            action_state.press(PlayerAction::Start);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.joystick_released = false;
+        self.swap_pressed = false;
+        self.pause_pressed = false;
+    }
+}
+
+#[derive(Component)]
+pub struct PauseButton {}
+
+pub struct TouchPlugin {}
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchControls>()
+            .add_systems(
+                PreUpdate,
+                touch_controls_system.in_set(InputManagerSystem::ManualControl),
+            )
+            .add_systems(
+                Update,
+                (spawn_pause_button_system, joystick_gizmo_system)
+                    .run_if(|controls: Res<TouchControls>| controls.active),
+            );
+    }
+}
+
+impl Default for TouchPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn touch_controls_system(
+    mut controls: ResMut<TouchControls>,
+    mut events: EventReader<TouchInput>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut player_query: Query<&mut ActionState<PlayerAction>, With<Player>>,
+    menu_action_state: Option<ResMut<ActionState<PlayerAction>>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    for event in events.read() {
+        controls.handle(event, window_size);
+    }
+    if !controls.active {
+        return;
+    }
+    for mut action_state in player_query.iter_mut() {
+        controls.apply(&mut action_state);
+    }
+    if let Some(mut action_state) = menu_action_state {
+        controls.apply(&mut action_state);
+    }
+    controls.clear();
+}
+
+pub fn spawn_pause_button_system(mut commands: Commands, query: Query<(), With<PauseButton>>) {
+    if !query.is_empty() {
+        return;
+    }
+    commands
+        .spawn((
+            PauseButton {},
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.),
+                    left: Val::Px(0.),
+                    width: Val::Px(PAUSE_BUTTON_SIZE),
+                    height: Val::Px(PAUSE_BUTTON_SIZE),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.2).into(),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "II",
+                TextStyle {
+                    font_size: 48.,
+                    color: Color::DARK_GRAY,
+                    ..default()
+                },
+            ));
+        });
+}
+
+pub fn joystick_gizmo_system(
+    mut gizmos: Gizmos,
+    controls: Res<TouchControls>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    let Some(joystick) = controls.joystick else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(origin) = camera.viewport_to_world_2d(camera_transform, joystick.origin) else {
+        return;
+    };
+    let knob = origin + joystick.axis() * JOYSTICK_RADIUS;
+    gizmos.circle_2d(origin, JOYSTICK_RADIUS, Color::rgba(0., 0., 0., 0.3));
+    gizmos.circle_2d(knob, JOYSTICK_RADIUS / 3., Color::rgba(0., 0., 0., 0.6));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::window::WindowResolution;
+
+    fn headless_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<TouchInput>()
+            .init_resource::<TouchControls>()
+            .add_systems(PreUpdate, touch_controls_system);
+        app.world.spawn((
+            Window {
+                resolution: WindowResolution::new(800., 600.),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        app
+    }
+
+    fn touch(phase: TouchPhase, id: u64, x: f32, y: f32) -> TouchInput {
+        TouchInput {
+            phase,
+            position: Vec2::new(x, y),
+            force: None,
+            id,
+        }
+    }
+
+    #[test]
+    fn drives_player_actions_from_touches() {
+        let mut app = headless_app();
+        let player = app
+            .world
+            .spawn((Player {}, ActionState::<PlayerAction>::default()))
+            .id();
+        app.world
+            .send_event(touch(TouchPhase::Started, 0, 200., 300.));
+        app.world
+            .send_event(touch(TouchPhase::Moved, 0, 240., 300.));
+        app.world
+            .send_event(touch(TouchPhase::Started, 1, 600., 300.));
+        app.update();
+
+        let action_state = app.world.get::<ActionState<PlayerAction>>(player).unwrap();
+        let axis = action_state
+            .axis_pair(PlayerAction::Move)
+            .map(|axis| axis.xy())
+            .unwrap_or_default();
+        assert!((axis - Vec2::new(0.5, 0.)).length() < 1e-4);
+        assert!(action_state.pressed(PlayerAction::SwapScale));
+        assert!(!action_state.pressed(PlayerAction::Pause));
+    }
+
+    #[test]
+    fn releases_joystick_when_touch_ends() {
+        let mut app = headless_app();
+        let player = app
+            .world
+            .spawn((Player {}, ActionState::<PlayerAction>::default()))
+            .id();
+        app.world
+            .send_event(touch(TouchPhase::Started, 0, 200., 300.));
+        app.world
+            .send_event(touch(TouchPhase::Moved, 0, 200., 220.));
+        app.update();
+        app.world
+            .send_event(touch(TouchPhase::Ended, 0, 200., 220.));
+        app.update();
+
+        let action_state = app.world.get::<ActionState<PlayerAction>>(player).unwrap();
+        assert_eq!(
+            action_state
+                .axis_pair(PlayerAction::Move)
+                .map(|axis| axis.xy()),
+            Some(Vec2::ZERO)
+        );
+        assert!(app.world.resource::<TouchControls>().joystick.is_none());
+    }
+}
@@ -0,0 +1,276 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{localization::LocalizedText, *};
+
+const SURVIVE_TIME: Duration = Duration::from_secs(5);
+const MOVE_DISTANCE: f32 = 200.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TutorialStep {
+    Move,
+    SwapScale,
+    EatSmaller,
+    AvoidBigger,
+    Ice,
+    Poison,
+    ScaleBust,
+    Done,
+}
+
+impl TutorialStep {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            Self::Move => "tutorial.move",
+            Self::SwapScale => "tutorial.swap-scale",
+            Self::EatSmaller => "tutorial.eat-smaller",
+            Self::AvoidBigger => "tutorial.avoid-bigger",
+            Self::Ice => "tutorial.ice",
+            Self::Poison => "tutorial.poison",
+            Self::ScaleBust => "tutorial.scale-bust",
+            Self::Done => "tutorial.done",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Move => Self::SwapScale,
+            Self::SwapScale => Self::EatSmaller,
+            Self::EatSmaller => Self::AvoidBigger,
+            Self::AvoidBigger => Self::Ice,
+            Self::Ice => Self::Poison,
+            Self::Poison => Self::ScaleBust,
+            Self::ScaleBust => Self::Done,
+            Self::Done => Self::Done,
+        }
+    }
+
+    pub fn spawns(&self, player: &Transform) -> Vec<(ObstacleKind, Vec2, f32)> {
+        let origin = player.translation.truncate();
+        let smaller = f32::max(player.scale.x * 0.5, MIN_SCALE);
+        let bigger = player.scale.x + 1.5;
+        let toward_center = if origin.y > 0. { -1. } else { 1. };
+        let spawns = match self {
+            Self::Move | Self::SwapScale | Self::Done => vec![],
+            Self::EatSmaller => vec![(ObstacleKind::ScaleBust(true), Vec2::new(0., 200.), smaller)],
+            Self::AvoidBigger => vec![(ObstacleKind::ScaleBust(true), Vec2::new(0., 300.), bigger)],
+            Self::Ice => vec![(ObstacleKind::Ice, Vec2::new(0., 200.), smaller)],
+            Self::Poison => vec![(ObstacleKind::Poison, Vec2::new(0., 250.), smaller)],
+            Self::ScaleBust => vec![
+                (
+                    ObstacleKind::ScaleBust(true),
+                    Vec2::new(-150., 200.),
+                    smaller,
+                ),
+                (
+                    ObstacleKind::ScaleBust(false),
+                    Vec2::new(150., 200.),
+                    smaller,
+                ),
+            ],
+        };
+        spawns
+            .into_iter()
+            .map(|(kind, offset, scale)| {
+                let half_size = arena::arena_inner_half_size() - ORIGINAL_RADIUS * scale;
+                let position = origin + Vec2::new(offset.x, offset.y * toward_center);
+                (kind, position.clamp(-half_size, half_size), scale)
+            })
+            .collect()
+    }
+}
+
+#[derive(Resource)]
+pub struct Tutorial {
+    step: TutorialStep,
+    started: bool,
+    timer: Timer,
+    travelled: f32,
+    last_position: Option<Vec2>,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            step: TutorialStep::Move,
+            started: false,
+            timer: Timer::new(SURVIVE_TIME, TimerMode::Once),
+            travelled: 0.,
+            last_position: None,
+        }
+    }
+}
+
+impl Tutorial {
+    pub fn at(step: TutorialStep) -> Self {
+        Self { step, ..default() }
+    }
+
+    pub fn step(&self) -> TutorialStep {
+        self.step
+    }
+
+    fn advance(&mut self) {
+        self.step = self.step.next();
+        self.restart_step();
+    }
+
+    fn restart_step(&mut self) {
+        self.started = false;
+        self.timer.reset();
+        self.travelled = 0.;
+        self.last_position = None;
+    }
+}
+
+#[derive(Component)]
+pub struct TutorialPrompt {}
+
+pub struct TutorialPlugin {}
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Startup),
+            (restart_tutorial_step_system, spawn_tutorial_prompt_system)
+                .run_if(resource_exists::<Tutorial>()),
+        )
+        .add_systems(
+            Update,
+            (tutorial_step_system, tutorial_prompt_system)
+                .chain()
+                .run_if(in_state(GameState::Running))
+                .run_if(resource_exists::<Tutorial>()),
+        );
+    }
+}
+
+impl Default for TutorialPlugin {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+pub fn restart_tutorial_step_system(mut tutorial: ResMut<Tutorial>) {
+    tutorial.restart_step();
+}
+
+pub fn spawn_tutorial_prompt_system(mut commands: Commands, tutorial: Res<Tutorial>) {
+    commands.spawn((
+        TutorialPrompt {},
+        LocalizedText {
+            key: tutorial.step.prompt(),
+        },
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 32.,
+                color: Color::DARK_GRAY,
+                ..default()
+            },
+        )
+        .with_text_alignment(TextAlignment::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(5.),
+            left: Val::Percent(5.),
+            right: Val::Percent(5.),
+            ..default()
+        }),
+    ));
+}
+
+pub fn tutorial_step_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tutorial: ResMut<Tutorial>,
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut spawn_events: EventWriter<SpawnObstacleEvent>,
+    player_query: Query<&Transform, With<Player>>,
+    obstacle_query: Query<Entity, With<Obstacle>>,
+    prompt_query: Query<Entity, With<TutorialPrompt>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    if !tutorial.started {
+        tutorial.started = true;
+        for entity in obstacle_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        for (kind, position, scale) in tutorial.step.spawns(player_transform) {
+            spawn_events.send(SpawnObstacleEvent {
+                color: kind.get_color(),
+                position: position.extend(0.),
+                radius: ORIGINAL_RADIUS,
+                scale,
+                kind,
+            });
+        }
+    }
+    let position = player_transform.translation.truncate();
+    if let Some(last_position) = tutorial.last_position {
+        tutorial.travelled += last_position.distance(position);
+    }
+    tutorial.last_position = Some(position);
+    tutorial.timer.tick(time.delta());
+
+    let step = tutorial.step;
+    if step == TutorialStep::Done {
+        if tutorial.timer.finished() {
+            commands.remove_resource::<Tutorial>();
+            for entity in prompt_query.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        return;
+    }
+    let completed = gameplay_events.read().fold(false, |completed, event| {
+        completed
+            || match (step, event) {
+                (TutorialStep::SwapScale, GameplayEvent::ScaleSwapped { .. }) => true,
+                (
+                    TutorialStep::EatSmaller,
+                    GameplayEvent::Absorbed {
+                        kind: ObstacleKind::ScaleBust(_),
+                        ..
+                    },
+                ) => true,
+                (
+                    TutorialStep::Ice,
+                    GameplayEvent::Absorbed {
+                        kind: ObstacleKind::Ice,
+                        ..
+                    },
+                ) => true,
+                (
+                    TutorialStep::ScaleBust,
+                    GameplayEvent::Absorbed {
+                        kind: ObstacleKind::ScaleBust(_),
+                        ..
+                    },
+                ) => true,
+                _ => false,
+            }
+    }) || match step {
+        TutorialStep::Move => tutorial.travelled >= MOVE_DISTANCE,
+        TutorialStep::AvoidBigger | TutorialStep::Poison => tutorial.timer.finished(),
+        _ => false,
+    };
+    if completed {
+        tutorial.advance();
+    }
+}
+
+pub fn tutorial_prompt_system(
+    tutorial: Res<Tutorial>,
+    mut query: Query<&mut LocalizedText, With<TutorialPrompt>>,
+) {
+    if !tutorial.is_changed() {
+        return;
+    }
+    for mut text in query.iter_mut() {
+        text.key = tutorial.step.prompt();
+    }
+}
@@ -29,5 +29,36 @@ fn main() {
         ..default()
     }))
     .add_plugins(GamePlugins);
+    if std::env::args().any(|arg| arg == "--arena") {
+        app.insert_resource(PlayField::Arena);
+    }
+    if std::env::args().any(|arg| arg == "--daily") {
+        let first_attempt_only = std::env::args().any(|arg| arg == "--first-attempt-only");
+        app.insert_resource(
+            daily::DailyChallenge::today().with_first_attempt_only(first_attempt_only),
+        );
+    }
+    if std::env::args().any(|arg| arg == "--tutorial") {
+        app.insert_resource(PlayField::Arena)
+            .insert_resource(tutorial::Tutorial::default());
+    }
+    if std::env::args().any(|arg| arg == "--telemetry") {
+        app.insert_resource(telemetry::TelemetryConfig {
+            path: Some("telemetry.jsonl".into()),
+        });
+    }
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(name) = args
+        .iter()
+        .position(|arg| arg == "--mode")
+        .and_then(|index| args.get(index + 1))
+    {
+        match game_mode::GameModeKind::from_name(name) {
+            Some(kind) => {
+                app.insert_resource(game_mode::GameModeConfig { kind });
+            }
+            None => eprintln!("unknown game mode '{}'", name),
+        }
+    }
     app.run();
 }